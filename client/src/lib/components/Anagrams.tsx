@@ -1,4 +1,4 @@
-import { Accessor, createSignal, For, onCleanup, onMount } from "solid-js";
+import { Accessor, createSignal, For, onCleanup, onMount, Show } from "solid-js";
 import { SetStoreFunction } from "solid-js/store";
 import { useEvents } from "../events";
 import { AnagramsState, Room, SendFn, State } from "../types/game";
@@ -91,6 +91,28 @@ export function Anagrams({
         });
       }
     },
+    AnagramsRoundEnded: (data) => {
+      setGame("players", (players) =>
+        players.map((player) => ({
+          ...player,
+          used_words: [],
+          points: data.leaderboard.find(([uuid]) => uuid === player.uuid)?.[1] ?? player.points,
+        })),
+      );
+      setGame({
+        intermission: true,
+        lastRound: {
+          originalWord: data.original_word,
+          missedWords: data.possible_words
+            .filter(([, foundBy]) => foundBy.length === 0)
+            .map(([word]) => word),
+        },
+      });
+    },
+    AnagramsRoundStarted: (data) => {
+      setGame({ round: data.round, anagram: data.anagram, intermission: false, lastRound: null });
+      inputElement.focus();
+    },
   });
 
   onMount(() => inputElement.focus());
@@ -115,17 +137,36 @@ export function Anagrams({
       <div class="relative flex items-center gap-x-8">
         <Leaderboard room={room} players={() => game().players} />
         <div class="w-[1px] self-stretch bg-dark-green/30"></div>
-        <div class="flex font-mono text-[36px]">
-          {[...game().anagram].map((char, i) => (
-            <h1
-              ref={letterElementMap[i]}
-              classList={{ "border-l-0": i !== 0 }}
-              class="flex h-16 w-16 items-center justify-center border"
-            >
-              {char}
-            </h1>
-          ))}
-        </div>
+        <Show
+          when={game().lastRound}
+          fallback={
+            <div class="flex font-mono text-[36px]">
+              <For each={[...game().anagram]}>
+                {(char, i) => (
+                  <h1
+                    ref={letterElementMap[i()]}
+                    classList={{ "border-l-0": i() !== 0 }}
+                    class="flex h-16 w-16 items-center justify-center border"
+                  >
+                    {char}
+                  </h1>
+                )}
+              </For>
+            </div>
+          }
+        >
+          {(lastRound) => (
+            <div class="flex max-w-md flex-col gap-y-2">
+              <h1 class="text-xl">
+                round {game().round} word:{" "}
+                <span class="text-light-green">{lastRound().originalWord}</span>
+              </h1>
+              <p class="max-h-64 overflow-y-auto text-dark-green">
+                missed: {lastRound().missedWords.join(", ") || "nothing!"}
+              </p>
+            </div>
+          )}
+        </Show>
         <h1
           ref={guessErrorElement}
           class="absolute -bottom-12 left-1/2 -translate-x-1/2 text-lg text-red-400 opacity-0"
@@ -138,8 +179,9 @@ export function Anagrams({
         size="lg"
         class="absolute bottom-6 left-1/2 -translate-x-1/2 focus-visible:border-white/10"
         placeholder={`words containing ${game().anagram}`}
-        maxlength="6"
+        maxlength={game().anagram.length}
         disabled={
+          game().intermission ||
           !game()
             .players.map(({ uuid }) => uuid)
             .includes(room().uuid)
@@ -154,7 +196,7 @@ export function Anagrams({
   );
 }
 
-function Leaderboard({
+export function Leaderboard({
   room,
   players,
 }: {
@@ -165,9 +207,9 @@ function Leaderboard({
     <div class="flex max-h-96 w-64 flex-col gap-y-1.5 overflow-y-auto text-lg">
       <For
         each={players()
-          .map(({ uuid, used_words }) => ({
+          .map(({ uuid, used_words, points }) => ({
             uuid,
-            score: used_words.reduce((acc, word) => acc + calculateAnagramsPoints(word), 0),
+            score: used_words.reduce((acc, word) => acc + calculateAnagramsPoints(word), points),
           }))
           .sort((a, b) => b.score - a.score)}
       >
@@ -0,0 +1,116 @@
+import { Accessor, createSignal, For, onCleanup, onMount } from "solid-js";
+import { SetStoreFunction } from "solid-js/store";
+import { useEvents } from "../events";
+import { BoggleState, Room, SendFn, State } from "../types/game";
+import { colors, cubicEasing, serverNow, translateBoggleGuessError } from "../utils";
+import { Leaderboard } from "./Anagrams";
+import { Input } from "./ui/Input";
+
+export function Boggle({
+  sendMsg,
+  room,
+  state,
+  setState,
+}: {
+  sendMsg: SendFn;
+  room: Accessor<Room>;
+  state: Accessor<State>;
+  setState: SetStoreFunction<State>;
+}) {
+  let inputElement!: HTMLInputElement;
+  let guessErrorElement!: HTMLHeadingElement;
+
+  const [game, setGame] = [
+    state as Accessor<BoggleState>,
+    setState as SetStoreFunction<BoggleState>,
+  ];
+  const [guessError, setGuessError] = createSignal("");
+  const [now, setNow] = createSignal(serverNow());
+
+  const timeLeft = () => Math.max(0, Math.ceil((game().deadline - now()) / 1000));
+  const self = () => game().players.find((player) => player.uuid === room().uuid);
+
+  const animateInput = (correct: boolean) => {
+    inputElement.animate(
+      { borderColor: [correct ? colors.green : colors.red, "rgb(255 255 255 / 0.1)"] },
+      { easing: cubicEasing, duration: 800 },
+    );
+  };
+
+  useEvents({
+    BoggleInvalidGuess: (data) => {
+      setGuessError(translateBoggleGuessError(data.reason));
+
+      animateInput(false);
+      guessErrorElement.animate(
+        { opacity: ["100%", "0%"] },
+        { easing: "ease-in", duration: 3000 },
+      );
+    },
+    BoggleCorrectGuess: (data) => {
+      setGame(
+        "players",
+        (player) => player.uuid === data.uuid,
+        "used_words",
+        (usedWords) => [...usedWords, data.guess],
+      );
+
+      if (data.uuid === room().uuid) {
+        inputElement.value = "";
+        animateInput(true);
+      }
+    },
+  });
+
+  const interval = setInterval(() => setNow(serverNow()), 250);
+
+  onMount(() => inputElement.focus());
+  onCleanup(() => clearInterval(interval));
+
+  return (
+    <main class="flex h-screen items-center justify-center">
+      <div class="relative flex items-center gap-x-8">
+        <Leaderboard room={room} players={() => game().players} />
+        <div class="w-[1px] self-stretch bg-dark-green/30"></div>
+        <div class="flex flex-col items-center gap-y-2">
+          <h1 class="text-light-green">{timeLeft()}s</h1>
+          <div class="flex flex-col gap-y-1.5 font-mono text-[32px] uppercase">
+            <For each={game().grid}>
+              {(row) => (
+                <div class="flex gap-x-1.5">
+                  <For each={row}>
+                    {(tile) => (
+                      <h1 class="flex h-14 w-14 items-center justify-center rounded-lg border">
+                        {tile}
+                      </h1>
+                    )}
+                  </For>
+                </div>
+              )}
+            </For>
+          </div>
+          <p class="text-dark-green">found {self()?.used_words.length ?? 0} words</p>
+        </div>
+        <h1
+          ref={guessErrorElement}
+          class="absolute -bottom-12 left-1/2 -translate-x-1/2 text-lg text-red-400 opacity-0"
+        >
+          {guessError()}
+        </h1>
+      </div>
+      <Input
+        ref={inputElement}
+        size="lg"
+        class="absolute bottom-6 left-1/2 -translate-x-1/2 focus-visible:border-white/10"
+        placeholder="trace words through neighbouring tiles"
+        maxlength={game().grid.length ** 2}
+        disabled={!self()}
+        onEnter={(input) => {
+          if (input.value.length !== 0) {
+            sendMsg({ type: "BoggleGuess", word: input.value });
+          }
+        }}
+      />
+    </main>
+  );
+}
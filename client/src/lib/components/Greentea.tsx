@@ -0,0 +1,192 @@
+import { Accessor, createSignal, For, onCleanup, onMount, Show } from "solid-js";
+import { SetStoreFunction } from "solid-js/store";
+import { useEvents } from "../events";
+import { GreenteaState, Room, SendFn, State } from "../types/game";
+import {
+  colors,
+  cubicEasing,
+  getClient,
+  getUsername,
+  serverNow,
+  translateGreenteaGuessError,
+} from "../utils";
+import { Avatar } from "./ui/Avatar";
+import { Input } from "./ui/Input";
+
+export function Greentea({
+  sendMsg,
+  room,
+  state,
+  setState,
+}: {
+  sendMsg: SendFn;
+  room: Accessor<Room>;
+  state: Accessor<State>;
+  setState: SetStoreFunction<State>;
+}) {
+  let inputElement!: HTMLInputElement;
+  let guessErrorElement!: HTMLHeadingElement;
+
+  const [game, setGame] = [
+    state as Accessor<GreenteaState>,
+    setState as SetStoreFunction<GreenteaState>,
+  ];
+  const [guessError, setGuessError] = createSignal("");
+  const [now, setNow] = createSignal(serverNow());
+
+  const timeLeft = () => Math.max(0, Math.ceil((game().deadline - now()) / 1000));
+  const submitted = () => game().submitted.includes(room().uuid);
+
+  const animateInput = (correct: boolean) => {
+    inputElement.animate(
+      { borderColor: [correct ? colors.green : colors.red, "rgb(255 255 255 / 0.1)"] },
+      { easing: cubicEasing, duration: 800 },
+    );
+  };
+
+  useEvents({
+    GreenteaInvalidGuess: (data) => {
+      setGuessError(translateGreenteaGuessError(data.reason));
+
+      animateInput(false);
+      guessErrorElement.animate(
+        { opacity: ["100%", "0%"] },
+        { easing: "ease-in", duration: 3000 },
+      );
+    },
+    GreenteaCorrectGuess: (data) => {
+      setGame("submitted", (submitted) => [...submitted, data.uuid]);
+
+      if (data.uuid === room().uuid) {
+        inputElement.value = `+${data.points}`;
+        animateInput(true);
+      }
+    },
+    GreenteaRoundEnded: (data) => {
+      setGame("players", (players) =>
+        players.map((player) => ({
+          ...player,
+          points: data.leaderboard.find(([uuid]) => uuid === player.uuid)?.[1] ?? player.points,
+        })),
+      );
+      setGame({
+        intermission: true,
+        submitted: [],
+        lastRound: { letters: data.letters, guesses: data.guesses },
+      });
+    },
+    GreenteaRoundStarted: (data) => {
+      setGame({
+        round: data.round,
+        letters: data.letters,
+        deadline: data.deadline,
+        intermission: false,
+        lastRound: null,
+      });
+
+      inputElement.value = "";
+      inputElement.focus();
+    },
+  });
+
+  const interval = setInterval(() => setNow(serverNow()), 250);
+
+  onMount(() => inputElement.focus());
+  onCleanup(() => clearInterval(interval));
+
+  return (
+    <main class="flex h-screen items-center justify-center">
+      <div class="relative flex items-center gap-x-8">
+        <div class="flex max-h-96 w-64 flex-col gap-y-1.5 overflow-y-auto text-lg">
+          <For each={[...game().players].sort((a, b) => b.points - a.points)}>
+            {({ uuid, points }, i) => {
+              const client = () => getClient(room(), uuid)!;
+
+              return (
+                <div
+                  classList={{ "opacity-50": client().disconnected }}
+                  class="flex items-center gap-x-1.5 transition-opacity"
+                >
+                  <h1 class="tabular-nums">{i() + 1}.</h1>
+                  <Avatar username={client().username} size={25} />
+                  <h1
+                    classList={{ "text-light-green": game().submitted.includes(uuid) }}
+                    class="min-w-4 flex-1 truncate"
+                  >
+                    {client().username}
+                  </h1>
+                  <h1 class="justify-self-end truncate text-light-green">{points}</h1>
+                </div>
+              );
+            }}
+          </For>
+        </div>
+        <div class="w-[1px] self-stretch bg-dark-green/30"></div>
+        <Show
+          when={game().lastRound}
+          fallback={
+            <div class="flex flex-col items-center gap-y-2">
+              <h1 class="text-light-green">
+                round {game().round} · {timeLeft()}s
+              </h1>
+              <div class="flex font-mono text-[36px]">
+                <For each={[...game().letters]}>
+                  {(char, i) => (
+                    <h1
+                      classList={{ "border-l-0": i() !== 0 }}
+                      class="flex h-16 w-16 items-center justify-center border"
+                    >
+                      {char}
+                    </h1>
+                  )}
+                </For>
+              </div>
+            </div>
+          }
+        >
+          {(lastRound) => (
+            <div class="flex max-w-md flex-col gap-y-2">
+              <h1 class="text-xl">
+                round {game().round} letters:{" "}
+                <span class="text-light-green">{lastRound().letters}</span>
+              </h1>
+              <For each={lastRound().guesses} fallback={<p class="text-dark-green">no words!</p>}>
+                {([uuid, word, points]) => (
+                  <p>
+                    {getUsername(room(), uuid)}: <span class="text-light-green">{word}</span> +
+                    {points}
+                  </p>
+                )}
+              </For>
+            </div>
+          )}
+        </Show>
+        <h1
+          ref={guessErrorElement}
+          class="absolute -bottom-12 left-1/2 -translate-x-1/2 text-lg text-red-400 opacity-0"
+        >
+          {guessError()}
+        </h1>
+      </div>
+      <Input
+        ref={inputElement}
+        size="lg"
+        class="absolute bottom-6 left-1/2 -translate-x-1/2 focus-visible:border-white/10"
+        placeholder={`a word containing ${game().letters}`}
+        maxlength={35}
+        disabled={
+          game().intermission ||
+          submitted() ||
+          !game()
+            .players.map(({ uuid }) => uuid)
+            .includes(room().uuid)
+        }
+        onEnter={(input) => {
+          if (input.value.length !== 0) {
+            sendMsg({ type: "GreenteaGuess", word: input.value });
+          }
+        }}
+      />
+    </main>
+  );
+}
@@ -17,9 +17,10 @@ import { Input } from "~/lib/components/ui/Input";
 import { useEvents } from "~/lib/events";
 import { Anagrams, Bomb } from "~/lib/icons";
 import { LobbyState, Room, SendFn, State } from "~/lib/types/game";
-import { PostGameInfo, Uuid } from "~/lib/types/messages";
+import { PostGameInfo, Uuid, WordBombTeamStats } from "~/lib/types/messages";
 import { colors, cubicEasing, getUsername, Variant } from "../utils";
 import { Settings } from "./Settings";
+import { teamColors } from "./WordBomb";
 import { Avatar } from "./ui/Avatar";
 
 export function Lobby({
@@ -73,6 +74,30 @@ export function Lobby({
                 info={postGameInfo as Variant<PostGameInfo, "Anagrams">}
               />
             </Match>
+            <Match when={postGameInfo!.type === "Greentea"}>
+              <GreenteaPostGameInfo
+                room={unwrap(room())}
+                info={postGameInfo as Variant<PostGameInfo, "Greentea">}
+              />
+            </Match>
+            <Match when={postGameInfo!.type === "WordChain"}>
+              <WordChainPostGameInfo
+                room={unwrap(room())}
+                info={postGameInfo as Variant<PostGameInfo, "WordChain">}
+              />
+            </Match>
+            <Match when={postGameInfo!.type === "Wordle"}>
+              <WordlePostGameInfo
+                room={unwrap(room())}
+                info={postGameInfo as Variant<PostGameInfo, "Wordle">}
+              />
+            </Match>
+            <Match when={postGameInfo!.type === "Boggle"}>
+              <BogglePostGameInfo
+                room={unwrap(room())}
+                info={postGameInfo as Variant<PostGameInfo, "Boggle">}
+              />
+            </Match>
           </Switch>
           <div class="w-[1px] self-stretch bg-dark-green/30"></div>
         </Show>
@@ -97,7 +122,12 @@ function WordBombPostGameInfo({
 }) {
   return (
     <div class="flex w-96 flex-col gap-y-2.5">
-      <Winner room={room} winner={info.winner} />
+      <Show
+        when={info.winning_team !== null}
+        fallback={<Winner room={room} winner={info.winner} />}
+      >
+        <WinningTeam room={room} team={info.winning_team!} stats={info.teams} />
+      </Show>
       <div class="grid grid-cols-2 gap-x-10 gap-y-2.5 overflow-y-auto">
         {(
           [
@@ -129,6 +159,46 @@ function WordBombPostGameInfo({
   );
 }
 
+function WordChainPostGameInfo({
+  room,
+  info,
+}: {
+  room: Room;
+  info: Variant<PostGameInfo, "WordChain">;
+}) {
+  return (
+    <div class="flex w-96 flex-col gap-y-2.5">
+      <Winner room={room} winner={info.winner} />
+      <div class="grid grid-cols-2 gap-x-10 gap-y-2.5 overflow-y-auto">
+        {(
+          [
+            [
+              "fastest guess",
+              info.fastest_guesses.map(
+                ([uuid, seconds]) => [uuid, `${seconds.toFixed(2)}s`] as [string, string],
+              ),
+            ],
+            ["longest word", info.longest_words],
+            ["average word length", info.avg_word_lengths],
+          ] as const
+        ).map(([title, items]) => (
+          <>
+            {items.length !== 0 && <WordBombLeaderboard room={room} title={title} items={items} />}
+          </>
+        ))}
+      </div>
+      <Stats>
+        <h1>
+          <span>{info.mins_elapsed.toFixed(1)}</span> mins
+        </h1>
+        <h1>
+          <span>{info.words_used}</span> words chained
+        </h1>
+      </Stats>
+    </div>
+  );
+}
+
 function AnagramsPostGameInfo({
   room,
   info,
@@ -140,6 +210,7 @@ function AnagramsPostGameInfo({
     <div class="flex w-64 flex-col gap-y-2">
       <h1 class="text-xl">Leaderboard</h1>
       <AnagramsLeaderboard room={room} info={info} />
+      <PossibleWords room={room} info={info} />
       <Stats>
         <h1>
           the original word was <span>{info.original_word}</span>
@@ -149,6 +220,28 @@ function AnagramsPostGameInfo({
   );
 }
 
+function BogglePostGameInfo({
+  room,
+  info,
+}: {
+  room: Room;
+  info: Variant<PostGameInfo, "Boggle">;
+}) {
+  return (
+    <div class="flex w-64 flex-col gap-y-2">
+      <h1 class="text-xl">Leaderboard</h1>
+      <AnagramsLeaderboard room={room} info={info} />
+      <PossibleWords room={room} info={info} />
+      <Stats>
+        <h1>
+          the grid was{" "}
+          <span class="font-mono">{info.grid.map((row) => row.join("")).join(" ")}</span>
+        </h1>
+      </Stats>
+    </div>
+  );
+}
+
 function Winner({ room, winner }: { room: Room; winner: Uuid }) {
   const username = getUsername(room, winner)!;
 
@@ -166,6 +259,43 @@ function Winner({ room, winner }: { room: Room; winner: Uuid }) {
   );
 }
 
+function WinningTeam({
+  room,
+  team,
+  stats,
+}: {
+  room: Room;
+  team: number;
+  stats: Array<WordBombTeamStats>;
+}) {
+  return (
+    <div class="flex flex-col gap-y-1.5">
+      <div class="flex items-center justify-between rounded-lg border p-2.5">
+        <h1 class="text-lg font-medium">
+          <span class={teamColors[team]}>team {team + 1}</span> wins!
+        </h1>
+        <div class="flex -space-x-2">
+          {stats[team]!.players.map((uuid) => (
+            <Avatar username={getUsername(room, uuid)!} size={35} />
+          ))}
+        </div>
+      </div>
+      {stats.map((teamStats, i) => (
+        <div class="flex items-center gap-x-2 text-sm">
+          <h1 class={`w-14 ${teamColors[i]}`}>team {i + 1}</h1>
+          <h1 class="min-w-4 flex-1 truncate">
+            {teamStats.players.map((uuid) => getUsername(room, uuid)).join(", ")}
+          </h1>
+          <h1 class="text-light-green">
+            {teamStats.words_used} words, {teamStats.extra_lives} extra lives
+            {teamStats.avg_wpm !== null && `, ${teamStats.avg_wpm.toFixed(1)} wpm`}
+          </h1>
+        </div>
+      ))}
+    </div>
+  );
+}
+
 function WordBombLeaderboard({
   room,
   title,
@@ -196,12 +326,94 @@ function WordBombLeaderboard({
   );
 }
 
+function GreenteaPostGameInfo({
+  room,
+  info,
+}: {
+  room: Room;
+  info: Variant<PostGameInfo, "Greentea">;
+}) {
+  return (
+    <div class="flex w-96 flex-col gap-y-2.5">
+      <Winner room={room} winner={info.leaderboard[0]![0]} />
+      <div class="flex flex-1 flex-col gap-y-1.5 overflow-y-auto">
+        {info.leaderboard.map(([uuid, score], i) => {
+          const username = getUsername(room, uuid)!;
+          const longestWord = info.longest_words.find((player) => player[0] === uuid)?.[1];
+
+          return (
+            <div class="flex flex-col gap-y-0.5">
+              <div class="flex items-center gap-x-1.5">
+                <h1 class="tabular-nums">{i + 1}.</h1>
+                <Avatar username={username} size={25} />
+                <h1 class="min-w-4 flex-1 truncate">{username}</h1>
+                <h1 class="justify-self-end truncate text-lightest-green">{score}</h1>
+              </div>
+              <Show when={longestWord}>
+                <p class="text-light-green">longest word: {longestWord}</p>
+              </Show>
+            </div>
+          );
+        })}
+      </div>
+      <Stats>
+        <h1>
+          last letters <span>{info.letters}</span>
+        </h1>
+      </Stats>
+    </div>
+  );
+}
+
+function WordlePostGameInfo({
+  room,
+  info,
+}: {
+  room: Room;
+  info: Variant<PostGameInfo, "Wordle">;
+}) {
+  return (
+    <div class="flex w-96 flex-col gap-y-2.5">
+      <Winner room={room} winner={info.standings[0]!.uuid} />
+      <div class="flex flex-1 flex-col gap-y-1.5 overflow-y-auto">
+        {info.standings.map(({ uuid, solved, guesses, secs }, i) => {
+          const username = getUsername(room, uuid)!;
+          const board = info.boards.find((player) => player[0] === uuid)?.[1] ?? [];
+
+          return (
+            <div class="flex flex-col gap-y-0.5">
+              <div class="flex items-center gap-x-1.5">
+                <h1 class="tabular-nums">{i + 1}.</h1>
+                <Avatar username={username} size={25} />
+                <h1 class="min-w-4 flex-1 truncate">{username}</h1>
+                <h1 class="justify-self-end truncate text-lightest-green">
+                  {solved ? `${guesses} guesses · ${secs!.toFixed(1)}s` : "unsolved"}
+                </h1>
+              </div>
+              <Show when={board.length !== 0}>
+                <p class="font-mono text-light-green">
+                  {board.map((guess) => guess.word).join(" ")}
+                </p>
+              </Show>
+            </div>
+          );
+        })}
+      </div>
+      <Stats>
+        <h1>
+          the word was <span>{info.word}</span>
+        </h1>
+      </Stats>
+    </div>
+  );
+}
+
 function AnagramsLeaderboard({
   room,
   info,
 }: {
   room: Room;
-  info: Variant<PostGameInfo, "Anagrams">;
+  info: Variant<PostGameInfo, "Anagrams" | "Boggle">;
 }) {
   return (
     <div class="flex flex-1 flex-col gap-y-1.5 overflow-y-auto">
@@ -225,6 +437,36 @@ function AnagramsLeaderboard({
   );
 }
 
+function PossibleWords({
+  room,
+  info,
+}: {
+  room: Room;
+  info: Variant<PostGameInfo, "Anagrams" | "Boggle">;
+}) {
+  return (
+    <div class="flex flex-col gap-y-1.5">
+      <h1>
+        {info.possible_words.length} possible words, found{" "}
+        {info.completion
+          .map(([uuid, completion]) => `${getUsername(room, uuid)} ${Math.round(completion * 100)}%`)
+          .join(", ")}
+      </h1>
+      <p class="max-h-32 overflow-y-auto text-sm">
+        {info.possible_words.map(([word, foundBy]) => (
+          <span
+            classList={{ "text-dark-green": foundBy.length === 0 }}
+            class="text-light-green"
+            title={foundBy.map((uuid) => getUsername(room, uuid)).join(", ")}
+          >
+            {word}{" "}
+          </span>
+        ))}
+      </p>
+    </div>
+  );
+}
+
 function Stats({ children }: { children: JSX.Element | Array<JSX.Element> }) {
   return (
     <div class="mt-auto flex justify-around text-center [&>h1>span]:text-lightest-green">
@@ -297,10 +539,10 @@ function JoinButtons({
           color="secondary"
           size="lg"
           class="flex-1"
-          disabled={lobby().ready.length < 2}
+          disabled={lobby().ready.length < (room().settings.ranked ? 2 : 1)}
           onClick={() => sendMsg({ type: "StartEarly" })}
         >
-          Start Early
+          {lobby().ready.length === 1 ? "Play Solo" : "Start Early"}
         </Button>
       </Show>
     </div>
@@ -318,7 +560,7 @@ function Practice({ sendMsg, room }: { sendMsg: SendFn; room: Accessor<Room> })
   const progress = () => {
     setPracticeSet(([, ...rest]) => setPracticeSet(rest));
 
-    if (room().settings.game === "Anagrams") {
+    if (room().settings.game === "Anagrams" || room().settings.game === "Boggle") {
       usedAnagrams = [];
     }
 
@@ -352,7 +594,12 @@ function Practice({ sendMsg, room }: { sendMsg: SendFn; room: Accessor<Room> })
   useEvents({
     PracticeSet: (data) => setPracticeSet(data.set),
     PracticeResult: (data) => {
-      if (data.correct && room().settings.game === "WordBomb") {
+      // anagrams and boggle prompts have more than one answer
+      if (
+        data.correct &&
+        room().settings.game !== "Anagrams" &&
+        room().settings.game !== "Boggle"
+      ) {
         progress();
       }
 
@@ -406,7 +653,7 @@ function Practice({ sendMsg, room }: { sendMsg: SendFn; room: Accessor<Room> })
           practiceInputElement.placeholder = "focus to continue";
         }}
         onEnter={(input) => {
-          if (room().settings.game === "Anagrams") {
+          if (room().settings.game === "Anagrams" || room().settings.game === "Boggle") {
             if (usedAnagrams.includes(input.value)) {
               animateInput(false);
               return;
@@ -2,17 +2,25 @@ import { Accessor, createSignal, onCleanup, Show } from "solid-js";
 import { useEvent } from "~/lib/events";
 import { Link } from "~/lib/icons";
 import { Room, SendFn } from "~/lib/types/game";
+import { setClockOffset } from "~/lib/utils";
 import { Avatar } from "./ui/Avatar";
 
 export function GameNav({ sendMsg, room }: { sendMsg: SendFn; room: Accessor<Room> }) {
-  const [ping, setPing] = createSignal(0);
+  const [ping, setPing] = createSignal<number | null>(null);
 
   const sendPing = () => sendMsg({ type: "Ping", timestamp: Date.now() });
   sendPing();
 
   const pingInterval = setInterval(sendPing, 10000);
 
-  useEvent("Pong", (data) => setPing(Date.now() - data.timestamp));
+  useEvent("Pong", (data) => {
+    const now = Date.now();
+    const rtt = now - data.timestamp;
+
+    setPing(rtt);
+    // assumes the pong took half the round trip to arrive
+    setClockOffset(data.server_time + rtt / 2 - now);
+  });
 
   onCleanup(() => clearInterval(pingInterval));
 
@@ -26,7 +34,7 @@ export function GameNav({ sendMsg, room }: { sendMsg: SendFn; room: Accessor<Roo
           style="box-shadow: 0px 0px 15.5px 1px #26D16C"
           class="h-[13px] w-[13px] rounded-full bg-green"
         />
-        <h1>{ping()}ms</h1>
+        <h1>{ping() ?? 0}ms</h1>
         <div class="flex -space-x-2">
           {room()
             .clients.slice(0, 3)
@@ -1,21 +1,46 @@
-import { Accessor, createSignal, onMount } from "solid-js";
+import { Accessor, createResource, createSignal, For, onMount, Show } from "solid-js";
 import { Settings as SettingsIcon } from "../icons";
 import { Room, SendFn } from "../types/game";
-import { Games } from "../types/messages";
+import { BotDifficulty, Games, WordBombVariant } from "../types/messages";
+import { url } from "../utils";
 import { Button } from "./ui/Button";
 import { Select } from "./ui/Select";
 
 export function Settings({ sendMsg, room }: { sendMsg: SendFn; room: Accessor<Room> }) {
   let gameElement!: HTMLSelectElement;
   let wordBombDifficultyElement!: HTMLSelectElement;
+  let wordBombLivesElement!: HTMLSelectElement;
+  let wordBombTeamsElement!: HTMLSelectElement;
+  let wordBombVariantElement!: HTMLSelectElement;
+  let anagramsRoundsElement!: HTMLSelectElement;
+  let anagramsLengthElement!: HTMLSelectElement;
+  let anagramsDifficultyElement!: HTMLSelectElement;
+  let greenteaRoundsElement!: HTMLSelectElement;
+  let wordChainLenElement!: HTMLSelectElement;
+  let wordleGuessesElement!: HTMLSelectElement;
+  let boggleSizeElement!: HTMLSelectElement;
 
   const [visible, setVisible] = createSignal(false);
+  const [dictionaries] = createResource<string[]>(async () => {
+    const res = await fetch(url("/api/info"));
+    return (await res.json()).dictionaries;
+  });
   const notRoomOwner = () => room().owner !== room().uuid;
 
   // https://github.com/solidjs/solid/issues/1754
   onMount(() => {
     gameElement.value = room().settings.game.toString();
     wordBombDifficultyElement.value = room().settings.word_bomb.min_wpm.toString();
+    wordBombLivesElement.value = room().settings.word_bomb.lives.toString();
+    wordBombTeamsElement.value = room().settings.word_bomb.teams?.toString() ?? "";
+    wordBombVariantElement.value = room().settings.word_bomb.variant;
+    anagramsRoundsElement.value = room().settings.anagrams.rounds.toString();
+    anagramsLengthElement.value = room().settings.anagrams.word_length.toString();
+    anagramsDifficultyElement.value = room().settings.anagrams.difficulty;
+    greenteaRoundsElement.value = room().settings.greentea.rounds.toString();
+    wordChainLenElement.value = room().settings.word_chain.chain_len.toString();
+    wordleGuessesElement.value = room().settings.wordle.max_guesses.toString();
+    boggleSizeElement.value = room().settings.boggle.size.toString();
   });
 
   return (
@@ -45,8 +70,39 @@ export function Settings({ sendMsg, room }: { sendMsg: SendFn; room: Accessor<Ro
             >
               <option value="WordBomb">Word Bomb</option>
               <option value="Anagrams">Anagrams</option>
+              <option value="Greentea">Greentea</option>
+              <option value="WordChain">Word Chain</option>
+              <option value="Wordle">Wordle</option>
+              <option value="Boggle">Boggle</option>
             </Select>
           </div>
+          {/* nothing to pick until the server has more than one dictionary */}
+          <Show when={(dictionaries()?.length ?? 0) > 1}>
+            <div class="flex items-center justify-between">
+              <label for="dictionary">dictionary</label>
+              <Select
+                size="xs"
+                name="dictionary"
+                id="dictionary"
+                disabled={notRoomOwner()}
+                onChange={(event) => {
+                  sendMsg({
+                    type: "RoomSettings",
+                    ...room().settings,
+                    dictionary: event.target.value,
+                  });
+                }}
+              >
+                <For each={dictionaries()}>
+                  {(dictionary) => (
+                    <option value={dictionary} selected={dictionary === room().settings.dictionary}>
+                      {dictionary}
+                    </option>
+                  )}
+                </For>
+              </Select>
+            </div>
+          </Show>
           <div class="flex items-center justify-between">
             <label for="visibility">public</label>
             <input
@@ -60,6 +116,19 @@ export function Settings({ sendMsg, room }: { sendMsg: SendFn; room: Accessor<Ro
               }}
             />
           </div>
+          <div class="flex items-center justify-between">
+            <label for="ranked">ranked</label>
+            <input
+              type="checkbox"
+              name="ranked"
+              id="ranked"
+              checked={room().settings.ranked}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({ type: "RoomSettings", ...room().settings, ranked: event.target.checked });
+              }}
+            />
+          </div>
         </div>
         <div class="h-[1px] w-full bg-dark-green/30"></div>
         <div class="space-y-1.5">
@@ -100,6 +169,347 @@ export function Settings({ sendMsg, room }: { sendMsg: SendFn; room: Accessor<Ro
               </optgroup>
             </Select>
           </div>
+          <div class="flex items-center justify-between">
+            <label for="lives">lives</label>
+            <Select
+              ref={wordBombLivesElement}
+              size="xs"
+              name="lives"
+              id="lives"
+              value={room().settings.word_bomb.lives}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  word_bomb: {
+                    ...room().settings.word_bomb,
+                    lives: parseInt(event.target.value),
+                  },
+                });
+              }}
+            >
+              {[1, 2, 3, 4, 5].map((lives) => (
+                <option value={lives}>{lives}</option>
+              ))}
+            </Select>
+          </div>
+          <div class="flex items-center justify-between">
+            <label for="teams">teams</label>
+            <Select
+              ref={wordBombTeamsElement}
+              size="xs"
+              name="teams"
+              id="teams"
+              value={room().settings.word_bomb.teams ?? ""}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  word_bomb: {
+                    ...room().settings.word_bomb,
+                    teams: event.target.value ? parseInt(event.target.value) : null,
+                  },
+                });
+              }}
+            >
+              <option value="">off</option>
+              {[2, 3, 4].map((teams) => (
+                <option value={teams}>{teams}</option>
+              ))}
+            </Select>
+          </div>
+          <div class="flex items-center justify-between">
+            <label for="variant">variant</label>
+            <Select
+              ref={wordBombVariantElement}
+              size="xs"
+              name="variant"
+              id="variant"
+              value={room().settings.word_bomb.variant}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  word_bomb: {
+                    ...room().settings.word_bomb,
+                    variant: event.target.value as WordBombVariant,
+                  },
+                });
+              }}
+            >
+              <option value="Classic">classic</option>
+              <option value="Reverse">reverse on extra life</option>
+              <option value="Hardcore">hardcore</option>
+              <option value="NoRepeats">no repeats</option>
+            </Select>
+          </div>
+          <div class="flex items-center justify-between">
+            <label for="power-ups">power ups</label>
+            <input
+              type="checkbox"
+              name="power-ups"
+              id="power-ups"
+              checked={room().settings.word_bomb.power_ups}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  word_bomb: { ...room().settings.word_bomb, power_ups: event.target.checked },
+                });
+              }}
+            />
+          </div>
+          <div class="flex items-center justify-between">
+            <label for="bots">bots</label>
+            <div class="flex items-center gap-x-1.5">
+              <Select
+                size="xs"
+                name="bots"
+                id="bots"
+                value=""
+                disabled={notRoomOwner() || room().settings.game !== "WordBomb"}
+                onChange={(event) => {
+                  sendMsg({ type: "AddBot", difficulty: event.target.value as BotDifficulty });
+                  event.target.value = "";
+                }}
+              >
+                <option value="" disabled>
+                  add
+                </option>
+                <option value="Easy">easy</option>
+                <option value="Medium">medium</option>
+                <option value="Hard">hard</option>
+              </Select>
+              <Button
+                color="muted"
+                size="sm"
+                class="py-0.5"
+                disabled={notRoomOwner() || !room().clients.some((client) => client.bot)}
+                onClick={() => {
+                  for (const client of room().clients.filter((client) => client.bot)) {
+                    sendMsg({ type: "RemoveBot", uuid: client.uuid });
+                  }
+                }}
+              >
+                clear
+              </Button>
+            </div>
+          </div>
+        </div>
+        <div class="h-[1px] w-full bg-dark-green/30"></div>
+        <div class="space-y-1.5">
+          <h1 class="pb-0.5 text-lg text-light-green">Anagrams</h1>
+          <div class="flex items-center justify-between">
+            <label for="rounds">rounds</label>
+            <Select
+              ref={anagramsRoundsElement}
+              size="xs"
+              name="rounds"
+              id="rounds"
+              value={room().settings.anagrams.rounds}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  anagrams: {
+                    ...room().settings.anagrams,
+                    rounds: parseInt(event.target.value),
+                  },
+                });
+              }}
+            >
+              {[1, 3, 5].map((rounds) => (
+                <option value={rounds}>{rounds}</option>
+              ))}
+            </Select>
+          </div>
+          <div class="flex items-center justify-between">
+            <label for="word-length">letters</label>
+            <Select
+              ref={anagramsLengthElement}
+              size="xs"
+              name="word-length"
+              id="word-length"
+              value={room().settings.anagrams.word_length}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  anagrams: {
+                    ...room().settings.anagrams,
+                    word_length: parseInt(event.target.value),
+                  },
+                });
+              }}
+            >
+              {[5, 6, 7, 8, 9].map((length) => (
+                <option value={length}>{length}</option>
+              ))}
+            </Select>
+          </div>
+          <div class="flex items-center justify-between">
+            <label for="anagrams-difficulty">difficulty</label>
+            <Select
+              ref={anagramsDifficultyElement}
+              size="xs"
+              name="anagrams-difficulty"
+              id="anagrams-difficulty"
+              value={room().settings.anagrams.difficulty}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  anagrams: {
+                    ...room().settings.anagrams,
+                    difficulty: event.target.value as "Easy" | "Medium" | "Hard",
+                  },
+                });
+              }}
+            >
+              <option value="Easy">easy</option>
+              <option value="Medium">medium</option>
+              <option value="Hard">hard</option>
+            </Select>
+          </div>
+        </div>
+        <div class="h-[1px] w-full bg-dark-green/30"></div>
+        <div class="space-y-1.5">
+          <h1 class="pb-0.5 text-lg text-light-green">Greentea</h1>
+          <div class="flex items-center justify-between">
+            <label for="greentea-rounds">rounds</label>
+            <Select
+              ref={greenteaRoundsElement}
+              size="xs"
+              name="greentea-rounds"
+              id="greentea-rounds"
+              value={room().settings.greentea.rounds}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  greentea: {
+                    ...room().settings.greentea,
+                    rounds: parseInt(event.target.value),
+                  },
+                });
+              }}
+            >
+              {[5, 8, 12].map((rounds) => (
+                <option value={rounds}>{rounds}</option>
+              ))}
+            </Select>
+          </div>
+        </div>
+        <div class="h-[1px] w-full bg-dark-green/30"></div>
+        <div class="space-y-1.5">
+          <h1 class="pb-0.5 text-lg text-light-green">Word Chain</h1>
+          <div class="flex items-center justify-between">
+            <label for="word-chain-len">chain letters</label>
+            <Select
+              ref={wordChainLenElement}
+              size="xs"
+              name="word-chain-len"
+              id="word-chain-len"
+              value={room().settings.word_chain.chain_len}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  word_chain: {
+                    ...room().settings.word_chain,
+                    chain_len: parseInt(event.target.value),
+                  },
+                });
+              }}
+            >
+              {[1, 2, 3].map((len) => (
+                <option value={len}>{len}</option>
+              ))}
+            </Select>
+          </div>
+        </div>
+        <div class="h-[1px] w-full bg-dark-green/30"></div>
+        <div class="space-y-1.5">
+          <h1 class="pb-0.5 text-lg text-light-green">Wordle</h1>
+          <div class="flex items-center justify-between">
+            <label for="wordle-guesses">guesses</label>
+            <Select
+              ref={wordleGuessesElement}
+              size="xs"
+              name="wordle-guesses"
+              id="wordle-guesses"
+              value={room().settings.wordle.max_guesses}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  wordle: {
+                    ...room().settings.wordle,
+                    max_guesses: parseInt(event.target.value),
+                  },
+                });
+              }}
+            >
+              {[4, 6, 8].map((guesses) => (
+                <option value={guesses}>{guesses}</option>
+              ))}
+            </Select>
+          </div>
+          <div class="flex items-center justify-between">
+            <label for="wordle-daily">daily word</label>
+            <input
+              type="checkbox"
+              name="wordle-daily"
+              id="wordle-daily"
+              checked={room().settings.wordle.daily}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  wordle: { ...room().settings.wordle, daily: event.target.checked },
+                });
+              }}
+            />
+          </div>
+        </div>
+        <div class="h-[1px] w-full bg-dark-green/30"></div>
+        <div class="space-y-1.5">
+          <h1 class="pb-0.5 text-lg text-light-green">Boggle</h1>
+          <div class="flex items-center justify-between">
+            <label for="boggle-size">grid size</label>
+            <Select
+              ref={boggleSizeElement}
+              size="xs"
+              name="boggle-size"
+              id="boggle-size"
+              value={room().settings.boggle.size}
+              disabled={notRoomOwner()}
+              onChange={(event) => {
+                sendMsg({
+                  type: "RoomSettings",
+                  ...room().settings,
+                  boggle: { ...room().settings.boggle, size: parseInt(event.target.value) },
+                });
+              }}
+            >
+              {[4, 5].map((size) => (
+                <option value={size}>
+                  {size}x{size}
+                </option>
+              ))}
+            </Select>
+          </div>
         </div>
         <Button
           color="muted"
@@ -1,15 +1,18 @@
 import { Accessor, For, onCleanup, onMount, Show } from "solid-js";
 import { SetStoreFunction } from "solid-js/store";
+import { Button } from "~/lib/components/ui/Button";
 import { Input } from "~/lib/components/ui/Input";
 import { useEvents } from "~/lib/events";
 import { Heart, LostHeart, SmallBomb } from "~/lib/icons";
 import { Room, SendFn, State, WordBombState } from "~/lib/types/game";
-import { Uuid, WordBombPlayerData } from "~/lib/types/messages";
-import { colors, cubicEasing, getClient } from "~/lib/utils";
+import { Uuid, WordBombPlayerData, WordChainPlayerData } from "~/lib/types/messages";
+import { colors, cubicEasing, getClient, powerUpNames } from "~/lib/utils";
 import { Avatar } from "./ui/Avatar";
 
 const keyboard = [[..."qwertyuiop"], [..."asdfghjkl"], [..."zxcvbnm"]];
 
+export const teamColors = ["text-green", "text-blue", "text-yellow-600", "text-red-400"];
+
 export function WordBomb({
   sendMsg,
   room,
@@ -30,6 +33,13 @@ export function WordBomb({
     setState as SetStoreFunction<WordBombState>,
   ];
 
+  // teammates share lives and letters
+  const teammates = (a: Uuid, b: Uuid) => {
+    const team = (uuid: Uuid) => game().players.find((player) => player.uuid === uuid)?.team;
+
+    return a === b || (team(a) != null && team(a) === team(b));
+  };
+
   const animateInput = (correct: boolean) => {
     inputElement.animate(
       { borderColor: [correct ? colors.green : colors.red, "rgb(255 255 255 / 0.1)"] },
@@ -100,21 +110,20 @@ export function WordBomb({
     WordBombPrompt: (data) => {
       const prevTurn = game().turn;
 
-      setGame({ prompt: data.prompt, turn: data.turn });
+      setGame({ prompt: data.prompt, turn: data.turn, deadline: data.deadline });
       setGame(
         "players",
-        (player) => player.uuid === prevTurn,
+        (player) => teammates(player.uuid, prevTurn),
         "lives",
         (lives) => lives + data.life_change,
       );
 
-      if (prevTurn === room().uuid && data.correct_guess) {
-        setGame("usedLetters", (usedLetters) => new Set([...usedLetters!, ...data.correct_guess!]));
-
-        const letters = keyboard.reduce((acc, keys) => acc + keys.length, 0);
-
-        if (data.life_change > 0 && game().usedLetters!.size === letters) {
+      if (teammates(prevTurn, room().uuid) && data.correct_guess) {
+        // the server only awards a life once the room's required letters are all used
+        if (data.life_change > 0) {
           setGame("usedLetters", new Set());
+        } else {
+          setGame("usedLetters", (usedLetters) => new Set([...usedLetters!, ...data.correct_guess!]));
         }
       }
 
@@ -131,8 +140,40 @@ export function WordBomb({
         inputElement.focus();
       }
     },
+    WordBombPowerUpEarned: (data) => {
+      setGame(
+        "players",
+        (player) => player.uuid === data.uuid,
+        "power_ups",
+        (powerUps) => [...powerUps, data.power_up],
+      );
+    },
+    WordBombPowerUpUsed: (data) => {
+      setGame(
+        "players",
+        (player) => player.uuid === data.uuid,
+        "power_ups",
+        (powerUps) => {
+          const index = powerUps.indexOf(data.power_up);
+
+          return powerUps.filter((_, i) => i !== index);
+        },
+      );
+      setGame({ turn: data.turn, deadline: data.deadline });
+
+      if (data.turn !== data.uuid) {
+        animateOverlay();
+
+        if (data.turn === room().uuid) {
+          inputElement.value = "";
+          inputElement.focus();
+        }
+      }
+    },
   });
 
+  const self = () => game().players.find((player) => player.uuid === room().uuid);
+
   onMount(() => {
     if (game().turn === room().uuid) {
       inputElement.focus();
@@ -175,6 +216,25 @@ export function WordBomb({
       >
         <SmallBomb class="absolute bottom-1.5 left-1.5 -translate-x-1/2 translate-y-1/2" />
       </div>
+      <Show when={self()?.power_ups.length}>
+        <div class="absolute bottom-24 left-1/2 flex -translate-x-1/2 gap-x-2">
+          <For each={self()!.power_ups}>
+            {(powerUp) => (
+              <Button
+                color="muted"
+                size="sm"
+                disabled={game().turn !== room().uuid}
+                onClick={() => {
+                  sendMsg({ type: "WordBombPowerUp", power_up: powerUp });
+                  inputElement.focus();
+                }}
+              >
+                {powerUpNames[powerUp]}
+              </Button>
+            )}
+          </For>
+        </div>
+      </Show>
       <Input
         ref={inputElement}
         size="lg"
@@ -194,7 +254,14 @@ export function WordBomb({
   );
 }
 
-function Player({ room, player }: { room: Accessor<Room>; player: WordBombPlayerData }) {
+// word chain players have no teams or power ups
+export function Player({
+  room,
+  player,
+}: {
+  room: Accessor<Room>;
+  player: WordChainPlayerData & Partial<Pick<WordBombPlayerData, "team" | "power_ups">>;
+}) {
   const client = () => getClient(room(), player.uuid)!;
 
   return (
@@ -206,7 +273,14 @@ function Player({ room, player }: { room: Accessor<Room>; player: WordBombPlayer
       <div class="flex items-center gap-x-4">
         <Avatar username={client().username} size={100} />
         <div class="flex flex-col gap-y-2">
-          <h1>{client().username}</h1>
+          <h1>
+            {client().username}
+            <Show when={player.team != null}>
+              <span class={`ml-2 text-base ${teamColors[player.team!]}`}>
+                team {player.team! + 1}
+              </span>
+            </Show>
+          </h1>
           <div class="flex gap-x-2">
             {Array.from({ length: player.lives }).map(() => (
               <Heart />
@@ -217,6 +291,11 @@ function Player({ room, player }: { room: Accessor<Room>; player: WordBombPlayer
           </div>
         </div>
       </div>
+      <Show when={player.power_ups && player.power_ups.length !== 0}>
+        <p class="text-sm text-light-green">
+          {player.power_ups!.map((powerUp) => powerUpNames[powerUp]).join(", ")}
+        </p>
+      </Show>
       <Show when={player.input.length !== 0}>
         <p class="quick-fade-in">{player.input}</p>
       </Show>
@@ -0,0 +1,134 @@
+import { Accessor, createSignal, For, onCleanup, onMount } from "solid-js";
+import { SetStoreFunction } from "solid-js/store";
+import { Input } from "~/lib/components/ui/Input";
+import { Player } from "~/lib/components/WordBomb";
+import { useEvents } from "~/lib/events";
+import { Room, SendFn, State, WordChainState } from "~/lib/types/game";
+import { Uuid } from "~/lib/types/messages";
+import { colors, cubicEasing, serverNow } from "~/lib/utils";
+
+export function WordChain({
+  sendMsg,
+  room,
+  state,
+  setState,
+}: {
+  sendMsg: SendFn;
+  room: Accessor<Room>;
+  state: Accessor<State>;
+  setState: SetStoreFunction<State>;
+}) {
+  let inputElement!: HTMLInputElement;
+
+  const [game, setGame] = [
+    state as Accessor<WordChainState>,
+    setState as SetStoreFunction<WordChainState>,
+  ];
+  const [lastWord, setLastWord] = createSignal<string | null>(null);
+  const [now, setNow] = createSignal(serverNow());
+
+  const timeLeft = () => Math.max(0, Math.ceil((game().deadline - now()) / 1000));
+
+  const animateInput = (correct: boolean) => {
+    inputElement.animate(
+      { borderColor: [correct ? colors.green : colors.red, "rgb(255 255 255 / 0.1)"] },
+      { easing: cubicEasing, duration: 800 },
+    );
+  };
+
+  const animatePlayer = (uuid: Uuid, correct: boolean) => {
+    document
+      .getElementById(uuid)
+      ?.animate(
+        { color: [correct ? colors.green : colors.red, "rgb(255 255 255)"] },
+        { easing: cubicEasing, duration: 800 },
+      );
+  };
+
+  useEvents({
+    WordChainInput: (data) => {
+      setGame("players", (player) => player.uuid === data.uuid, "input", data.input);
+    },
+    WordChainInvalidGuess: (data) => {
+      animatePlayer(data.uuid, false);
+
+      if (data.uuid === room().uuid) {
+        animateInput(false);
+      }
+    },
+    WordChainPrompt: (data) => {
+      const prevTurn = game().turn;
+
+      setGame({ prompt: data.prompt, turn: data.turn, deadline: data.deadline });
+      setGame(
+        "players",
+        (player) => player.uuid === prevTurn,
+        "lives",
+        (lives) => lives + data.life_change,
+      );
+      setLastWord(data.correct_guess);
+
+      const guessIsCorrect = data.correct_guess !== null;
+
+      if (prevTurn === room().uuid) {
+        animateInput(guessIsCorrect);
+      }
+      animatePlayer(prevTurn, guessIsCorrect);
+
+      if (data.turn === room().uuid) {
+        inputElement.value = "";
+        inputElement.focus();
+      }
+    },
+  });
+
+  const interval = setInterval(() => setNow(serverNow()), 250);
+
+  onMount(() => {
+    if (game().turn === room().uuid) {
+      inputElement.focus();
+    }
+  });
+  onCleanup(() => clearInterval(interval));
+
+  return (
+    <main class="flex h-full flex-col justify-start overflow-hidden">
+      <div
+        style="background: linear-gradient(185deg, rgba(38, 209, 108, 0.5) 7.28%, rgba(76, 118, 93, 0.1) 82.41%);"
+        class="flex h-24 w-full flex-col items-center justify-center font-mono"
+      >
+        <h1 class="text-[34px]">{game().prompt}...</h1>
+        <p class="text-sm text-light-green">
+          {lastWord() ? `after ${lastWord()} · ` : ""}
+          {timeLeft()}s
+        </p>
+      </div>
+      <div class="flex h-full w-full flex-wrap items-center justify-around gap-x-12 gap-y-4 overflow-y-auto p-8">
+        <For each={game().players}>
+          {(player) => (
+            <div
+              classList={{ "rounded-xl border border-dark-green": game().turn === player.uuid }}
+              class="p-4"
+            >
+              <Player room={room} player={player} />
+            </div>
+          )}
+        </For>
+      </div>
+      <Input
+        ref={inputElement}
+        size="lg"
+        class="absolute bottom-6 left-1/2 -translate-x-1/2 focus-visible:border-white/10"
+        placeholder={`a word starting with ${game().prompt}`}
+        maxlength="35"
+        disabled={game().turn !== room().uuid}
+        onInput={(event) => sendMsg({ type: "WordChainInput", input: event.target.value })}
+        onEnter={(input) => {
+          if (input.value.length !== 0) {
+            sendMsg({ type: "WordChainGuess", word: input.value });
+          }
+        }}
+      />
+    </main>
+  );
+}
@@ -0,0 +1,173 @@
+import { Accessor, createSignal, For, Index, onCleanup, onMount, Show } from "solid-js";
+import { SetStoreFunction } from "solid-js/store";
+import { useEvents } from "../events";
+import { Room, SendFn, State, WordleState } from "../types/game";
+import { WordleFeedback } from "../types/messages";
+import { colors, cubicEasing, getClient, serverNow, translateWordleGuessError } from "../utils";
+import { Avatar } from "./ui/Avatar";
+import { Input } from "./ui/Input";
+
+const WORD_LEN = 5;
+
+const feedbackColors: Record<WordleFeedback, string> = {
+  Correct: "bg-green border-green",
+  Present: "bg-yellow-600 border-yellow-600",
+  Absent: "bg-dark-green/30",
+};
+
+export function Wordle({
+  sendMsg,
+  room,
+  state,
+  setState,
+}: {
+  sendMsg: SendFn;
+  room: Accessor<Room>;
+  state: Accessor<State>;
+  setState: SetStoreFunction<State>;
+}) {
+  let inputElement!: HTMLInputElement;
+  let guessErrorElement!: HTMLHeadingElement;
+
+  const [game, setGame] = [
+    state as Accessor<WordleState>,
+    setState as SetStoreFunction<WordleState>,
+  ];
+  const [guessError, setGuessError] = createSignal("");
+  const [now, setNow] = createSignal(serverNow());
+
+  const maxGuesses = () => room().settings.wordle.max_guesses;
+  const timeLeft = () => Math.max(0, Math.ceil((game().deadline - now()) / 1000));
+  const self = () => game().players.find((player) => player.uuid === room().uuid);
+  const finished = () => {
+    const player = self();
+
+    return !player || player.solved || player.guesses >= maxGuesses();
+  };
+
+  const animateInput = (correct: boolean) => {
+    inputElement.animate(
+      { borderColor: [correct ? colors.green : colors.red, "rgb(255 255 255 / 0.1)"] },
+      { easing: cubicEasing, duration: 800 },
+    );
+  };
+
+  useEvents({
+    WordleInvalidGuess: (data) => {
+      setGuessError(translateWordleGuessError(data.reason));
+
+      animateInput(false);
+      guessErrorElement.animate(
+        { opacity: ["100%", "0%"] },
+        { easing: "ease-in", duration: 3000 },
+      );
+    },
+    WordleFeedback: (data) => {
+      setGame("board", (board) => [...board, data.guess]);
+
+      inputElement.value = "";
+      animateInput(data.guess.feedback.every((letter) => letter === "Correct"));
+    },
+    WordleProgress: (data) => {
+      setGame("players", (player) => player.uuid === data.uuid, {
+        guesses: data.guesses,
+        correct: data.correct,
+        solved: data.solved,
+      });
+    },
+  });
+
+  const interval = setInterval(() => setNow(serverNow()), 250);
+
+  onMount(() => inputElement.focus());
+  onCleanup(() => clearInterval(interval));
+
+  return (
+    <main class="flex h-screen items-center justify-center">
+      <div class="relative flex items-center gap-x-8">
+        <div class="flex max-h-96 w-64 flex-col gap-y-2.5 overflow-y-auto">
+          <For each={game().players}>
+            {(player) => {
+              const client = () => getClient(room(), player.uuid)!;
+
+              return (
+                <div
+                  classList={{ "opacity-50": client().disconnected }}
+                  class="flex flex-col gap-y-1 transition-opacity"
+                >
+                  <div class="flex items-center gap-x-1.5">
+                    <Avatar username={client().username} size={25} />
+                    <h1
+                      classList={{ "text-light-green": player.solved }}
+                      class="min-w-4 flex-1 truncate"
+                    >
+                      {client().username}
+                    </h1>
+                    <h1 class="justify-self-end tabular-nums text-light-green">
+                      {player.guesses}/{maxGuesses()}
+                    </h1>
+                  </div>
+                  <div class="h-1.5 w-full rounded-full bg-dark-green/30">
+                    <div
+                      style={{ width: `${(player.correct / WORD_LEN) * 100}%` }}
+                      class="h-full rounded-full bg-green transition-[width]"
+                    ></div>
+                  </div>
+                </div>
+              );
+            }}
+          </For>
+        </div>
+        <div class="w-[1px] self-stretch bg-dark-green/30"></div>
+        <div class="flex flex-col items-center gap-y-2">
+          <h1 class="text-light-green">{timeLeft()}s</h1>
+          <div class="flex flex-col gap-y-1.5 font-mono text-2xl uppercase">
+            <Index each={Array.from({ length: maxGuesses() })}>
+              {(_, row) => (
+                <div class="flex gap-x-1.5">
+                  <Index each={Array.from({ length: WORD_LEN })}>
+                    {(_, col) => {
+                      const guess = () => game().board[row];
+
+                      return (
+                        <h1
+                          class={`flex h-12 w-12 items-center justify-center border ${
+                            guess() ? feedbackColors[guess()!.feedback[col]!] : ""
+                          }`}
+                        >
+                          {guess()?.word[col]}
+                        </h1>
+                      );
+                    }}
+                  </Index>
+                </div>
+              )}
+            </Index>
+          </div>
+          <Show when={self()?.solved}>
+            <p class="text-lightest-green">solved!</p>
+          </Show>
+        </div>
+        <h1
+          ref={guessErrorElement}
+          class="absolute -bottom-12 left-1/2 -translate-x-1/2 text-lg text-red-400 opacity-0"
+        >
+          {guessError()}
+        </h1>
+      </div>
+      <Input
+        ref={inputElement}
+        size="lg"
+        class="absolute bottom-6 left-1/2 -translate-x-1/2 focus-visible:border-white/10"
+        placeholder="guess the 5 letter word"
+        maxlength={WORD_LEN}
+        disabled={finished()}
+        onEnter={(input) => {
+          if (input.value.length !== 0) {
+            sendMsg({ type: "WordleGuess", word: input.value });
+          }
+        }}
+      />
+    </main>
+  );
+}
@@ -19,13 +19,31 @@ const listeners: {
   PracticeSet: [],
   PracticeResult: [],
   RoomSettings: [],
+  QueueUpdate: [],
+  MatchFound: [],
   GameStarted: [],
   GameEnded: [],
   WordBombInput: [],
   WordBombInvalidGuess: [],
   WordBombPrompt: [],
+  WordBombPowerUpEarned: [],
+  WordBombPowerUpUsed: [],
   AnagramsInvalidGuess: [],
   AnagramsCorrectGuess: [],
+  AnagramsRoundEnded: [],
+  AnagramsRoundStarted: [],
+  GreenteaInvalidGuess: [],
+  GreenteaCorrectGuess: [],
+  GreenteaRoundEnded: [],
+  GreenteaRoundStarted: [],
+  WordChainInput: [],
+  WordChainInvalidGuess: [],
+  WordChainPrompt: [],
+  WordleInvalidGuess: [],
+  WordleFeedback: [],
+  WordleProgress: [],
+  BoggleInvalidGuess: [],
+  BoggleCorrectGuess: [],
 };
 
 const unactedMessages: {
@@ -1,10 +1,14 @@
 import {
   AnagramsPlayerData,
   ClientInfo,
+  GreenteaPlayerData,
   ClientMessage,
   RoomSettings,
   Uuid,
   WordBombPlayerData,
+  WordChainPlayerData,
+  WordleGuess,
+  WordlePlayerData,
 } from "./messages";
 
 export type SendFn = (message: ClientMessage) => void;
@@ -37,6 +41,8 @@ export type WordBombState = {
   players: Array<WordBombPlayerData>;
   turn: Uuid;
   prompt: string;
+  // server clock, see `serverNow`
+  deadline: number;
   usedLetters: Set<string> | null;
 };
 
@@ -44,6 +50,55 @@ export type AnagramsState = {
   type: "Anagrams";
   players: Array<AnagramsPlayerData>;
   anagram: string;
+  round: number;
+  intermission: boolean;
+  // shown during the intermission
+  lastRound: { originalWord: string; missedWords: Array<string> } | null;
 };
 
-export type State = LobbyState | WordBombState | AnagramsState;
+export type GreenteaState = {
+  type: "Greentea";
+  players: Array<GreenteaPlayerData>;
+  letters: string;
+  round: number;
+  intermission: boolean;
+  // server clock, see `serverNow`
+  deadline: number;
+  submitted: Array<Uuid>;
+  // shown during the intermission
+  lastRound: { letters: string; guesses: Array<[Uuid, string, number]> } | null;
+};
+
+export type WordChainState = {
+  type: "WordChain";
+  players: Array<WordChainPlayerData>;
+  turn: Uuid;
+  prompt: string;
+  // server clock, see `serverNow`
+  deadline: number;
+};
+
+export type WordleState = {
+  type: "Wordle";
+  players: Array<WordlePlayerData>;
+  // server clock, see `serverNow`
+  deadline: number;
+  board: Array<WordleGuess>;
+};
+
+export type BoggleState = {
+  type: "Boggle";
+  players: Array<AnagramsPlayerData>;
+  grid: Array<Array<string>>;
+  // server clock, see `serverNow`
+  deadline: number;
+};
+
+export type State =
+  | LobbyState
+  | WordBombState
+  | AnagramsState
+  | GreenteaState
+  | WordChainState
+  | WordleState
+  | BoggleState;
@@ -1,6 +1,8 @@
 export type Uuid = string;
 
-export type Games = "WordBomb" | "Anagrams";
+export type Games = "WordBomb" | "Anagrams" | "Greentea" | "WordChain" | "Wordle" | "Boggle";
+
+export type BotDifficulty = "Easy" | "Medium" | "Hard";
 
 export type ClientMessage =
   | { type: "Ping"; timestamp: number }
@@ -11,15 +13,24 @@ export type ClientMessage =
   | { type: "PracticeRequest"; game: Games }
   | { type: "PracticeSubmission"; game: Games; prompt: string; input: string }
   | ({ type: "RoomSettings" } & RoomSettings)
+  | { type: "AddBot"; difficulty: BotDifficulty }
+  | { type: "RemoveBot"; uuid: Uuid }
   | { type: "WordBombInput"; input: string }
   | { type: "WordBombGuess"; word: string }
-  | { type: "AnagramsGuess"; word: string };
+  | { type: "WordBombPowerUp"; power_up: PowerUp }
+  | { type: "AnagramsGuess"; word: string }
+  | { type: "GreenteaGuess"; word: string }
+  | { type: "WordChainInput"; input: string }
+  | { type: "WordChainGuess"; word: string }
+  | { type: "WordleGuess"; word: string }
+  | { type: "BoggleGuess"; word: string };
 
 export type ServerMessage =
   // lobby / generic
   | {
       type: "Pong";
       timestamp: number;
+      server_time: number;
     }
   | {
       type: "Info";
@@ -60,6 +71,14 @@ export type ServerMessage =
   | ({
       type: "RoomSettings";
     } & RoomSettings)
+  | {
+      type: "QueueUpdate";
+      waiting: number;
+    }
+  | {
+      type: "MatchFound";
+      room: string;
+    }
   | {
       type: "GameStarted";
       rejoin_token: string | null;
@@ -87,6 +106,19 @@ export type ServerMessage =
       life_change: number;
       prompt: string;
       turn: Uuid;
+      deadline: number;
+    }
+  | {
+      type: "WordBombPowerUpEarned";
+      uuid: Uuid;
+      power_up: PowerUp;
+    }
+  | {
+      type: "WordBombPowerUpUsed";
+      uuid: Uuid;
+      power_up: PowerUp;
+      turn: Uuid;
+      deadline: number;
     }
   // anagrams
   | {
@@ -98,6 +130,87 @@ export type ServerMessage =
       uuid: Uuid;
       guess: string;
       points: number;
+    }
+  | {
+      type: "AnagramsRoundEnded";
+      round: number;
+      original_word: string;
+      possible_words: Array<[string, Array<Uuid>]>;
+      completion: Array<[Uuid, number]>;
+      leaderboard: Array<[Uuid, number]>;
+    }
+  | {
+      type: "AnagramsRoundStarted";
+      round: number;
+      anagram: string;
+    }
+  // greentea
+  | {
+      type: "GreenteaInvalidGuess";
+      reason: GreenteaGuessInfo;
+    }
+  | {
+      type: "GreenteaCorrectGuess";
+      uuid: Uuid;
+      points: number;
+    }
+  | {
+      type: "GreenteaRoundEnded";
+      round: number;
+      letters: string;
+      guesses: Array<[Uuid, string, number]>;
+      leaderboard: Array<[Uuid, number]>;
+    }
+  | {
+      type: "GreenteaRoundStarted";
+      round: number;
+      letters: string;
+      deadline: number;
+    }
+  // word chain
+  | {
+      type: "WordChainInput";
+      uuid: Uuid;
+      input: string;
+    }
+  | {
+      type: "WordChainInvalidGuess";
+      uuid: Uuid;
+      reason: WordChainGuessInfo;
+    }
+  | {
+      type: "WordChainPrompt";
+      correct_guess: string | null;
+      life_change: number;
+      prompt: string;
+      turn: Uuid;
+      deadline: number;
+    }
+  // wordle
+  | {
+      type: "WordleInvalidGuess";
+      reason: WordleGuessInfo;
+    }
+  | {
+      type: "WordleFeedback";
+      guess: WordleGuess;
+    }
+  | {
+      type: "WordleProgress";
+      uuid: Uuid;
+      guesses: number;
+      correct: number;
+      solved: boolean;
+    }
+  // boggle
+  | {
+      type: "BoggleInvalidGuess";
+      reason: BoggleGuessInfo;
+    }
+  | {
+      type: "BoggleCorrectGuess";
+      uuid: Uuid;
+      guess: string;
     };
 
 export type RoomInfo = {
@@ -109,14 +222,67 @@ export type RoomInfo = {
 
 export type RoomSettings = {
   public: boolean;
+  ranked: boolean;
   game: Games;
+  dictionary: string;
   word_bomb: WordBombSettings;
+  anagrams: AnagramsSettings;
+  greentea: GreenteaSettings;
+  word_chain: WordChainSettings;
+  wordle: WordleSettings;
+  boggle: BoggleSettings;
+};
+
+type BoggleSettings = {
+  size: number;
+  round_len: number;
+  min_guess_len: number;
+};
+
+type WordleSettings = {
+  max_guesses: number;
+  time_limit: number;
+  daily: boolean;
+};
+
+type WordChainSettings = {
+  lives: number;
+  chain_len: number;
+  min_timer_len: number;
+  max_timer_len: number;
+  min_turn_len: number;
+};
+
+type GreenteaSettings = {
+  rounds: number;
+  round_len: number;
+  min_wpm: number;
+};
+
+type AnagramsSettings = {
+  rounds: number;
+  word_length: number;
+  round_len: number;
+  min_guess_len: number;
+  difficulty: "Easy" | "Medium" | "Hard";
 };
 
 type WordBombSettings = {
   min_wpm: number;
+  lives: number;
+  max_lives: number | null;
+  min_timer_len: number;
+  max_timer_len: number;
+  min_turn_len: number;
+  prompt_uses: number;
+  excluded_letters: Array<string>;
+  teams: number | null;
+  power_ups: boolean;
+  variant: WordBombVariant;
 };
 
+export type WordBombVariant = "Classic" | "Reverse" | "Hardcore" | "NoRepeats";
+
 export type RoomStateInfo =
   | {
       type: "Lobby";
@@ -128,18 +294,51 @@ export type RoomStateInfo =
       players: Array<WordBombPlayerData>;
       turn: Uuid;
       prompt: string;
+      deadline: number;
       used_letters: Array<string> | null;
     }
   | {
       type: "Anagrams";
       players: Array<AnagramsPlayerData>;
       anagram: string;
+      round: number;
+      intermission: boolean;
+    }
+  | {
+      type: "Greentea";
+      players: Array<GreenteaPlayerData>;
+      letters: string;
+      round: number;
+      intermission: boolean;
+      deadline: number;
+      submitted: Array<Uuid>;
+    }
+  | {
+      type: "WordChain";
+      players: Array<WordChainPlayerData>;
+      turn: Uuid;
+      prompt: string;
+      deadline: number;
+    }
+  | {
+      type: "Wordle";
+      players: Array<WordlePlayerData>;
+      deadline: number;
+      board: Array<WordleGuess>;
+    }
+  | {
+      type: "Boggle";
+      players: Array<AnagramsPlayerData>;
+      grid: Array<Array<string>>;
+      deadline: number;
     };
 
 export type PostGameInfo =
   | {
       type: "WordBomb";
       winner: Uuid;
+      winning_team: number | null;
+      teams: Array<WordBombTeamStats>;
       mins_elapsed: number;
       words_used: number;
       fastest_guesses: Array<[Uuid, number]>;
@@ -152,22 +351,67 @@ export type PostGameInfo =
       original_word: string;
       leaderboard: Array<[Uuid, number]>;
       used_words: Array<[Uuid, Array<string>]>;
+      possible_words: Array<[string, Array<Uuid>]>;
+      completion: Array<[Uuid, number]>;
+    }
+  | {
+      type: "Greentea";
+      letters: string;
+      guesses: Array<[Uuid, string, number]>;
+      leaderboard: Array<[Uuid, number]>;
+      longest_words: Array<[Uuid, string]>;
+    }
+  | {
+      type: "WordChain";
+      winner: Uuid;
+      mins_elapsed: number;
+      words_used: number;
+      fastest_guesses: Array<[Uuid, number]>;
+      longest_words: Array<[Uuid, string]>;
+      avg_word_lengths: Array<[Uuid, number]>;
+    }
+  | {
+      type: "Wordle";
+      word: string;
+      standings: Array<{ uuid: Uuid; solved: boolean; guesses: number; secs: number | null }>;
+      boards: Array<[Uuid, Array<WordleGuess>]>;
+    }
+  | {
+      type: "Boggle";
+      grid: Array<Array<string>>;
+      leaderboard: Array<[Uuid, number]>;
+      used_words: Array<[Uuid, Array<string>]>;
+      possible_words: Array<[string, Array<Uuid>]>;
+      completion: Array<[Uuid, number]>;
     };
 
 export type ClientInfo = {
   uuid: Uuid;
   username: string;
   disconnected: boolean;
+  account: AccountInfo | null;
+  verified: boolean;
+  bot: boolean;
+};
+
+export type AccountInfo = {
+  username: string;
+  avatar_hash: string;
 };
 
 type ConnectionUpdate =
   | {
       type: "Connected";
       username: string;
+      account: AccountInfo | null;
+      verified: boolean;
+      bot: boolean;
     }
   | {
       type: "Reconnected";
       username: string;
+      account: AccountInfo | null;
+      verified: boolean;
     }
   | {
       type: "Disconnected";
@@ -187,6 +431,23 @@ export type WordBombPlayerData = {
   uuid: Uuid;
   input: string;
   lives: number;
+  team: number | null;
+  power_ups: Array<PowerUp>;
+};
+
+export type WordChainPlayerData = {
+  uuid: Uuid;
+  input: string;
+  lives: number;
+};
+
+export type PowerUp = "SkipTurn" | "ReverseOrder" | "AddTime" | "HarderPrompt";
+
+export type WordBombTeamStats = {
+  players: Array<Uuid>;
+  words_used: number;
+  extra_lives: number;
+  avg_wpm: number | null;
 };
 
 type WordBombGuessInfo =
@@ -194,15 +455,22 @@ type WordBombGuessInfo =
       type: "PromptNotIn";
     }
   | {
-      type: "NotEnglish";
+      type: "NotInDictionary";
     }
   | {
       type: "AlreadyUsed";
+    }
+  | {
+      type: "NotLongerThanLast";
+    }
+  | {
+      type: "UsedInEarlierGame";
     };
 
 export type AnagramsPlayerData = {
   uuid: Uuid;
   used_words: Array<string>;
+  points: number;
 };
 
 export type AnagramsGuessInfo =
@@ -213,8 +481,81 @@ export type AnagramsGuessInfo =
       type: "PromptMismatch";
     }
   | {
-      type: "NotEnglish";
+      type: "NotInDictionary";
+    }
+  | {
+      type: "AlreadyUsed";
+    };
+
+export type GreenteaPlayerData = {
+  uuid: Uuid;
+  points: number;
+};
+
+export type GreenteaGuessInfo =
+  | {
+      type: "PromptMismatch";
+    }
+  | {
+      type: "NotInDictionary";
+    }
+  | {
+      type: "Valid";
+      points: number;
+    };
+
+type WordChainGuessInfo =
+  | {
+      type: "PromptMismatch";
+    }
+  | {
+      type: "NotLongEnough";
+    }
+  | {
+      type: "NotInDictionary";
     }
   | {
       type: "AlreadyUsed";
     };
+
+export type WordlePlayerData = {
+  uuid: Uuid;
+  guesses: number;
+  correct: number;
+  solved: boolean;
+};
+
+export type WordleFeedback = "Correct" | "Present" | "Absent";
+
+export type WordleGuess = {
+  word: string;
+  feedback: Array<WordleFeedback>;
+};
+
+export type WordleGuessInfo =
+  | {
+      type: "WrongLength";
+    }
+  | {
+      type: "NotInDictionary";
+    }
+  | {
+      type: "Valid";
+    };
+
+export type BoggleGuessInfo =
+  | {
+      type: "NotLongEnough";
+    }
+  | {
+      type: "NotOnGrid";
+    }
+  | {
+      type: "NotInDictionary";
+    }
+  | {
+      type: "AlreadyUsed";
+    }
+  | {
+      type: "Valid";
+    };
@@ -1,5 +1,16 @@
+import { createSignal } from "solid-js";
 import { Room, State } from "~/lib/types/game";
-import { AnagramsGuessInfo, ClientInfo, RoomStateInfo, Uuid } from "~/lib/types/messages";
+import {
+  AnagramsGuessInfo,
+  BoggleGuessInfo,
+  ClientInfo,
+  Games,
+  GreenteaGuessInfo,
+  PowerUp,
+  RoomStateInfo,
+  Uuid,
+  WordleGuessInfo,
+} from "~/lib/types/messages";
 
 export type Variant<T, U> = Extract<T, { type: U }>;
 
@@ -13,6 +24,11 @@ export const colors = {
   red: "rgb(220 38 38)",
 };
 
+// difference between the server clock and ours, estimated from ping/pong
+export const [clockOffset, setClockOffset] = createSignal(0);
+
+export const serverNow = () => Date.now() + clockOffset();
+
 export type ErrorType = "socket closed";
 
 export class GameError extends Error {
@@ -74,22 +90,51 @@ export function convertStateMessage(state: RoomStateInfo): State {
       };
     }
     case "WordBomb": {
-      const { type, players, turn, prompt, used_letters } = state;
+      const { type, players, turn, prompt, deadline, used_letters } = state;
 
       return {
         type,
         players,
         turn,
         prompt,
+        deadline,
         usedLetters: new Set(used_letters),
       };
     }
     case "Anagrams": {
+      return { ...state, lastRound: null };
+    }
+    case "Greentea": {
+      return { ...state, lastRound: null };
+    }
+    case "WordChain": {
+      return state;
+    }
+    case "Wordle": {
+      return state;
+    }
+    case "Boggle": {
       return state;
     }
   }
 }
 
+export const gameNames: Record<Games, string> = {
+  WordBomb: "Word Bomb",
+  Anagrams: "Anagrams",
+  Greentea: "Greentea",
+  WordChain: "Word Chain",
+  Wordle: "Wordle",
+  Boggle: "Boggle",
+};
+
+export const powerUpNames: Record<PowerUp, string> = {
+  SkipTurn: "skip turn",
+  ReverseOrder: "reverse order",
+  AddTime: "+5 seconds",
+  HarderPrompt: "harder prompt",
+};
+
 export function getClient(room: Room, uuid: Uuid): ClientInfo | undefined {
   return room.clients.find((client) => client.uuid === uuid);
 }
@@ -106,13 +151,50 @@ export function translateAnagramsGuessError(guessInfo: AnagramsGuessInfo) {
       return "not long enough";
     case "PromptMismatch":
       return "word doesn't contain anagram";
-    case "NotEnglish":
-      return "word isn't valid english";
+    case "NotInDictionary":
+      return "word isn't in the dictionary";
     case "AlreadyUsed":
       return "word already used";
   }
 }
 
+export function translateGreenteaGuessError(guessInfo: GreenteaGuessInfo) {
+  switch (guessInfo.type) {
+    case "PromptMismatch":
+      return "word doesn't contain every letter";
+    case "NotInDictionary":
+      return "word isn't in the dictionary";
+    case "Valid":
+      return "";
+  }
+}
+
+export function translateWordleGuessError(guessInfo: WordleGuessInfo) {
+  switch (guessInfo.type) {
+    case "WrongLength":
+      return "word must be 5 letters";
+    case "NotInDictionary":
+      return "word isn't in the dictionary";
+    case "Valid":
+      return "";
+  }
+}
+
+export function translateBoggleGuessError(guessInfo: BoggleGuessInfo) {
+  switch (guessInfo.type) {
+    case "NotLongEnough":
+      return "word is too short";
+    case "NotOnGrid":
+      return "word can't be traced on the grid";
+    case "NotInDictionary":
+      return "word isn't in the dictionary";
+    case "AlreadyUsed":
+      return "word was already used";
+    case "Valid":
+      return "";
+  }
+}
+
 export function removeNonAlphanumeric(input: string) {
   return input.replace(/[^a-zA-Z0-9]/g, "");
 }
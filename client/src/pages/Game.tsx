@@ -11,12 +11,16 @@ import {
 import { createStore } from "solid-js/store";
 import { Anagrams } from "~/lib/components/Anagrams";
 import { Chat } from "~/lib/components/Chat";
+import { Boggle } from "~/lib/components/Boggle";
 import { error, ErrorDisplay, setError } from "~/lib/components/Error";
+import { Greentea } from "~/lib/components/Greentea";
 import { Lobby } from "~/lib/components/Lobby";
 import { GameNav } from "~/lib/components/Nav";
 import { Button } from "~/lib/components/ui/Button";
 import { Input } from "~/lib/components/ui/Input";
 import { WordBomb } from "~/lib/components/WordBomb";
+import { WordChain } from "~/lib/components/WordChain";
+import { Wordle } from "~/lib/components/Wordle";
 import { callEventListeners, ServerMessageData, useEvent, useEvents } from "~/lib/events";
 import { ChatMessage, ChatMessageType, Room, SendFn, State } from "~/lib/types/game";
 import { ClientMessage, PostGameInfo } from "~/lib/types/messages";
@@ -201,6 +205,9 @@ function Game({ uuid, room: roomInfo, sendMsg }: ServerMessageData<"Info"> & { s
           uuid: data.uuid,
           username: data.state.username,
           disconnected: false,
+          account: data.state.account,
+          verified: data.state.verified,
+          bot: data.state.type === "Connected" && data.state.bot,
         };
         setRoom("clients", (clients) => [
           ...clients.filter((client) => client.uuid !== data.uuid),
@@ -259,6 +266,18 @@ function Game({ uuid, room: roomInfo, sendMsg }: ServerMessageData<"Info"> & { s
         <Match when={state.type === "Anagrams"}>
           <Anagrams sendMsg={sendMsg} room={() => room} state={() => state} setState={setState} />
         </Match>
+        <Match when={state.type === "Greentea"}>
+          <Greentea sendMsg={sendMsg} room={() => room} state={() => state} setState={setState} />
+        </Match>
+        <Match when={state.type === "WordChain"}>
+          <WordChain sendMsg={sendMsg} room={() => room} state={() => state} setState={setState} />
+        </Match>
+        <Match when={state.type === "Wordle"}>
+          <Wordle sendMsg={sendMsg} room={() => room} state={() => state} setState={setState} />
+        </Match>
+        <Match when={state.type === "Boggle"}>
+          <Boggle sendMsg={sendMsg} room={() => room} state={() => state} setState={setState} />
+        </Match>
       </Switch>
     </div>
   );
@@ -5,7 +5,7 @@ import { Button } from "~/lib/components/ui/Button";
 import { Input } from "~/lib/components/ui/Input";
 import { GitHub } from "~/lib/icons";
 import { Games } from "~/lib/types/messages";
-import { cloneElement, url } from "~/lib/utils";
+import { cloneElement, gameNames, url } from "~/lib/utils";
 
 type Info = {
   clients_connected: number;
@@ -230,9 +230,7 @@ function Room(props: { name: string; players: Array<string>; game: Games }) {
     >
       <div>
         <h1 class="text-lg">{props.name}</h1>
-        <h1 class="text-sm text-light-green">
-          {props.game === "WordBomb" ? "Word Bomb" : "Anagrams"}
-        </h1>
+        <h1 class="text-sm text-light-green">{gameNames[props.game]}</h1>
       </div>
       <div class="flex -space-x-2">
         {props.players.slice(0, 3).map((player) => (
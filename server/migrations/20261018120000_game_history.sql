@@ -0,0 +1,33 @@
+-- every finished game, written once when the room returns to the lobby
+create table games(
+    game_id integer primary key autoincrement not null,
+    room text not null,
+    game text not null,
+    settings text not null,
+    started_at integer not null,
+    duration_ms integer not null
+);
+
+-- placement 1 is the winner
+create table game_players(
+    game_id integer not null,
+    uuid text not null,
+    username text not null,
+    discord_id text,
+    placement integer not null,
+    primary key (game_id, uuid),
+    foreign key (game_id) references games(game_id),
+    foreign key (discord_id) references users(discord_id)
+);
+
+-- time_ms is time spent on the turn for word bomb and time since round start for anagrams
+create table game_words(
+    game_id integer not null,
+    uuid text not null,
+    word text not null,
+    time_ms integer not null,
+    foreign key (game_id) references games(game_id)
+);
+
+create index game_players_discord_id on game_players(discord_id);
+create index games_room on games(room);
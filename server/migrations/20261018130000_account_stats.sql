@@ -0,0 +1,25 @@
+-- lifetime totals per account, updated from every finished game
+create table word_bomb_stats(
+    discord_id text primary key not null,
+    games_played integer not null,
+    wins integer not null,
+    words_used integer not null,
+    letters_used integer not null,
+    -- sum of every word's wpm, divide by words_used for the average
+    wpm_sum real not null,
+    best_wpm real not null,
+    fastest_guess_ms integer,
+    longest_word text not null,
+    foreign key (discord_id) references users(discord_id)
+);
+
+create table anagrams_stats(
+    discord_id text primary key not null,
+    games_played integer not null,
+    wins integer not null,
+    points integer not null,
+    best_points integer not null,
+    words_found integer not null,
+    longest_word text not null,
+    foreign key (discord_id) references users(discord_id)
+);
@@ -0,0 +1,13 @@
+-- one row per account per game, only for games with at least two signed in players
+create table leaderboard_results(
+    discord_id text not null,
+    game text not null,
+    ended_at integer not null,
+    won integer not null,
+    points integer not null,
+    wpm_sum real not null,
+    words_used integer not null,
+    foreign key (discord_id) references users(discord_id)
+);
+
+create index leaderboard_results_game on leaderboard_results(game, ended_at);
@@ -0,0 +1,9 @@
+-- elo rating per account per game, only updated by ranked games
+create table ratings(
+    discord_id text not null,
+    game text not null,
+    rating real not null,
+    games_played integer not null,
+    primary key (discord_id, game),
+    foreign key (discord_id) references users(discord_id)
+);
@@ -0,0 +1,6 @@
+-- gzipped json array of [ms since start, server message] pairs
+create table replays(
+    game_id integer primary key not null,
+    data blob not null,
+    foreign key (game_id) references games(game_id)
+);
@@ -0,0 +1,14 @@
+-- best solo result per account per game
+create table personal_bests(
+    discord_id text not null,
+    game text not null,
+    -- words used in word bomb and word chain, points in the other games
+    score integer not null,
+    -- word bomb and word chain only
+    survival_ms integer,
+    -- anagrams and boggle only, share of the possible words found
+    completion real,
+    achieved_at integer not null,
+    primary key (discord_id, game),
+    foreign key (discord_id) references users(discord_id)
+);
@@ -0,0 +1,13 @@
+-- words admins took out of a dictionary, applied on top of its file
+create table denied_words(
+    dictionary text not null,
+    word text not null,
+    primary key (dictionary, word)
+);
+
+-- words admins put into a dictionary that its file doesn't have
+create table added_words(
+    dictionary text not null,
+    word text not null,
+    primary key (dictionary, word)
+);
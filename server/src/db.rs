@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+use serde::Serialize;
 use sqlx::{
     sqlite::{SqliteConnectOptions, SqliteJournalMode, SqliteQueryResult},
     Pool, Result, Sqlite, SqlitePool,
@@ -21,7 +22,7 @@ pub async fn create_pool() -> Result<Pool<Sqlite>> {
     Ok(pool)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct User {
     pub discord_id: String,
     pub username: String,
@@ -43,6 +44,154 @@ pub struct Session {
     pub discord_id: String,
 }
 
+#[derive(Debug)]
+pub struct NewGame {
+    pub room: String,
+    pub game: &'static str,
+    pub settings: String,
+    pub started_at: u32,
+    pub duration_ms: u32,
+    pub players: Vec<NewGamePlayer>,
+    pub words: Vec<NewGameWord>,
+}
+
+#[derive(Debug)]
+pub struct NewGamePlayer {
+    pub uuid: String,
+    pub username: String,
+    pub discord_id: Option<String>,
+    pub placement: u32,
+}
+
+#[derive(Debug)]
+pub struct NewGameWord {
+    pub uuid: String,
+    pub word: String,
+    pub time_ms: u32,
+}
+
+#[derive(Debug)]
+pub enum StatsUpdate {
+    WordBomb(WordBombStatsUpdate),
+    Anagrams(AnagramsStatsUpdate),
+}
+
+#[derive(Debug)]
+pub struct WordBombStatsUpdate {
+    pub discord_id: String,
+    pub won: bool,
+    pub words_used: u32,
+    pub letters_used: u32,
+    pub avg_wpm: f32,
+    pub fastest_guess_ms: Option<u32>,
+    pub longest_word: String,
+}
+
+#[derive(Debug)]
+pub struct AnagramsStatsUpdate {
+    pub discord_id: String,
+    pub won: bool,
+    pub points: u32,
+    pub words_found: u32,
+    pub longest_word: String,
+}
+
+#[derive(Debug)]
+pub struct PersonalBestUpdate {
+    pub discord_id: String,
+    pub game: &'static str,
+    pub score: u32,
+    pub survival_ms: Option<u32>,
+    pub completion: Option<f32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PersonalBest {
+    pub game: String,
+    pub score: i64,
+    pub survival_ms: Option<i64>,
+    pub completion: Option<f64>,
+    pub achieved_at: i64,
+}
+
+#[derive(Debug)]
+pub struct WordBombStats {
+    pub games_played: i64,
+    pub wins: i64,
+    pub words_used: i64,
+    pub letters_used: i64,
+    pub wpm_sum: f64,
+    pub best_wpm: f64,
+    pub fastest_guess_ms: Option<i64>,
+    pub longest_word: String,
+}
+
+#[derive(Debug)]
+pub struct AnagramsStats {
+    pub games_played: i64,
+    pub wins: i64,
+    pub points: i64,
+    pub best_points: i64,
+    pub words_found: i64,
+    pub longest_word: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Rating {
+    pub game: String,
+    pub rating: f64,
+    pub games_played: i64,
+}
+
+#[derive(Debug)]
+pub struct LeaderboardRow {
+    pub username: String,
+    pub avatar_hash: String,
+    pub games_played: i64,
+    pub wins: i64,
+    pub points: i64,
+    pub wpm_sum: f64,
+    pub words_used: i64,
+}
+
+#[derive(Debug)]
+pub struct HistoryRow {
+    pub game_id: i64,
+    pub room: String,
+    pub game: String,
+    pub started_at: i64,
+    pub duration_ms: i64,
+    pub uuid: String,
+    pub username: String,
+    pub account: Option<String>,
+    pub placement: i64,
+}
+
+#[derive(Debug)]
+pub struct Game {
+    pub game_id: i64,
+    pub room: String,
+    pub game: String,
+    pub settings: String,
+    pub started_at: i64,
+    pub duration_ms: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GamePlayer {
+    pub uuid: String,
+    pub username: String,
+    pub account: Option<String>,
+    pub placement: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GameWord {
+    pub uuid: String,
+    pub word: String,
+    pub time_ms: i64,
+}
+
 pub async fn get_user(pool: &SqlitePool, discord_id: &str) -> Result<User> {
     let query = sqlx::query_as!(
         User,
@@ -55,6 +204,18 @@ pub async fn get_user(pool: &SqlitePool, discord_id: &str) -> Result<User> {
     Ok(query)
 }
 
+pub async fn get_user_by_username(pool: &SqlitePool, username: &str) -> Result<User> {
+    let query = sqlx::query_as!(
+        User,
+        "select * from users where username is ? collate nocase",
+        username,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(query)
+}
+
 pub async fn get_user_from_session(pool: &SqlitePool, session_id: &str) -> Result<User> {
     let query = sqlx::query_as!(
         User,
@@ -184,3 +345,606 @@ pub async fn delete_expired_sessions(pool: &SqlitePool) -> Result<SqliteQueryRes
 
     Ok(query)
 }
+
+pub async fn insert_game(pool: &SqlitePool, game: &NewGame) -> Result<i64> {
+    let mut tx = pool.begin().await?;
+
+    let game_id = sqlx::query!(
+        "insert into games (room, game, settings, started_at, duration_ms) values (?, ?, ?, ?, ?)",
+        game.room,
+        game.game,
+        game.settings,
+        game.started_at,
+        game.duration_ms,
+    )
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    for player in &game.players {
+        sqlx::query!(
+            "insert into game_players (game_id, uuid, username, discord_id, placement) values (?, ?, ?, ?, ?)",
+            game_id,
+            player.uuid,
+            player.username,
+            player.discord_id,
+            player.placement,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for word in &game.words {
+        sqlx::query!(
+            "insert into game_words (game_id, uuid, word, time_ms) values (?, ?, ?, ?)",
+            game_id,
+            word.uuid,
+            word.word,
+            word.time_ms,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(game_id)
+}
+
+pub async fn get_user_history(
+    pool: &SqlitePool,
+    discord_id: &str,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<HistoryRow>> {
+    let query = sqlx::query_as!(
+        HistoryRow,
+        r#"
+            select
+                games.game_id,
+                games.room,
+                games.game,
+                games.started_at,
+                games.duration_ms,
+                game_players.uuid,
+                game_players.username,
+                users.username as "account?",
+                game_players.placement
+            from
+                games
+                inner join game_players using (game_id)
+                left join users using (discord_id)
+            where
+                games.game_id in (
+                    select game_id from game_players where discord_id is ?
+                    order by game_id desc limit ? offset ?
+                )
+            order by
+                games.game_id desc, game_players.placement
+        "#,
+        discord_id,
+        limit,
+        offset,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_room_history(
+    pool: &SqlitePool,
+    room: &str,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<HistoryRow>> {
+    let query = sqlx::query_as!(
+        HistoryRow,
+        r#"
+            select
+                games.game_id,
+                games.room,
+                games.game,
+                games.started_at,
+                games.duration_ms,
+                game_players.uuid,
+                game_players.username,
+                users.username as "account?",
+                game_players.placement
+            from
+                games
+                inner join game_players using (game_id)
+                left join users using (discord_id)
+            where
+                games.game_id in (
+                    select game_id from games where room is ?
+                    order by game_id desc limit ? offset ?
+                )
+            order by
+                games.game_id desc, game_players.placement
+        "#,
+        room,
+        limit,
+        offset,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_game(pool: &SqlitePool, game_id: i64) -> Result<Game> {
+    let query = sqlx::query_as!(Game, "select * from games where game_id is ?", game_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(query)
+}
+
+pub async fn get_game_players(pool: &SqlitePool, game_id: i64) -> Result<Vec<GamePlayer>> {
+    let query = sqlx::query_as!(
+        GamePlayer,
+        r#"
+            select
+                game_players.uuid,
+                game_players.username,
+                users.username as "account?",
+                game_players.placement
+            from
+                game_players
+                left join users using (discord_id)
+            where
+                game_players.game_id is ?
+            order by
+                game_players.placement
+        "#,
+        game_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_game_words(pool: &SqlitePool, game_id: i64) -> Result<Vec<GameWord>> {
+    let query = sqlx::query_as!(
+        GameWord,
+        "select uuid, word, time_ms from game_words where game_id is ? order by rowid",
+        game_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn insert_replay(
+    pool: &SqlitePool,
+    game_id: i64,
+    data: &[u8],
+) -> Result<SqliteQueryResult> {
+    let query = sqlx::query!(
+        "insert into replays (game_id, data) values (?, ?)",
+        game_id,
+        data,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_replay(pool: &SqlitePool, game_id: i64) -> Result<Vec<u8>> {
+    let query = sqlx::query_scalar!("select data from replays where game_id is ?", game_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(query)
+}
+
+pub async fn update_stats(pool: &SqlitePool, update: &StatsUpdate) -> Result<SqliteQueryResult> {
+    match update {
+        StatsUpdate::WordBomb(stats) => update_word_bomb_stats(pool, stats).await,
+        StatsUpdate::Anagrams(stats) => update_anagrams_stats(pool, stats).await,
+    }
+}
+
+async fn update_word_bomb_stats(
+    pool: &SqlitePool,
+    stats: &WordBombStatsUpdate,
+) -> Result<SqliteQueryResult> {
+    let wpm_sum = stats.avg_wpm * stats.words_used as f32;
+
+    let query = sqlx::query!(
+        "
+            insert into word_bomb_stats
+                (discord_id, games_played, wins, words_used, letters_used, wpm_sum, best_wpm, fastest_guess_ms, longest_word)
+            values
+                (?, 1, ?, ?, ?, ?, ?, ?, ?)
+            on conflict (discord_id) do update set
+                games_played = games_played + 1,
+                wins = wins + excluded.wins,
+                words_used = words_used + excluded.words_used,
+                letters_used = letters_used + excluded.letters_used,
+                wpm_sum = wpm_sum + excluded.wpm_sum,
+                best_wpm = max(best_wpm, excluded.best_wpm),
+                fastest_guess_ms = min(
+                    coalesce(fastest_guess_ms, excluded.fastest_guess_ms),
+                    coalesce(excluded.fastest_guess_ms, fastest_guess_ms)
+                ),
+                longest_word = iif(
+                    length(excluded.longest_word) > length(longest_word),
+                    excluded.longest_word,
+                    longest_word
+                )
+        ",
+        stats.discord_id,
+        stats.won,
+        stats.words_used,
+        stats.letters_used,
+        wpm_sum,
+        stats.avg_wpm,
+        stats.fastest_guess_ms,
+        stats.longest_word,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(query)
+}
+
+async fn update_anagrams_stats(
+    pool: &SqlitePool,
+    stats: &AnagramsStatsUpdate,
+) -> Result<SqliteQueryResult> {
+    let query = sqlx::query!(
+        "
+            insert into anagrams_stats
+                (discord_id, games_played, wins, points, best_points, words_found, longest_word)
+            values
+                (?, 1, ?, ?, ?, ?, ?)
+            on conflict (discord_id) do update set
+                games_played = games_played + 1,
+                wins = wins + excluded.wins,
+                points = points + excluded.points,
+                best_points = max(best_points, excluded.best_points),
+                words_found = words_found + excluded.words_found,
+                longest_word = iif(
+                    length(excluded.longest_word) > length(longest_word),
+                    excluded.longest_word,
+                    longest_word
+                )
+        ",
+        stats.discord_id,
+        stats.won,
+        stats.points,
+        stats.points,
+        stats.words_found,
+        stats.longest_word,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_word_bomb_stats(
+    pool: &SqlitePool,
+    discord_id: &str,
+) -> Result<Option<WordBombStats>> {
+    let query = sqlx::query_as!(
+        WordBombStats,
+        "
+            select
+                games_played, wins, words_used, letters_used, wpm_sum, best_wpm, fastest_guess_ms, longest_word
+            from
+                word_bomb_stats
+            where
+                discord_id is ?
+        ",
+        discord_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_anagrams_stats(
+    pool: &SqlitePool,
+    discord_id: &str,
+) -> Result<Option<AnagramsStats>> {
+    let query = sqlx::query_as!(
+        AnagramsStats,
+        "
+            select
+                games_played, wins, points, best_points, words_found, longest_word
+            from
+                anagrams_stats
+            where
+                discord_id is ?
+        ",
+        discord_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn insert_leaderboard_result(
+    pool: &SqlitePool,
+    game: &str,
+    ended_at: u32,
+    update: &StatsUpdate,
+) -> Result<SqliteQueryResult> {
+    let (discord_id, won, points, wpm_sum, words_used) = match update {
+        StatsUpdate::WordBomb(stats) => (
+            &stats.discord_id,
+            stats.won,
+            0,
+            stats.avg_wpm * stats.words_used as f32,
+            stats.words_used,
+        ),
+        StatsUpdate::Anagrams(stats) => (
+            &stats.discord_id,
+            stats.won,
+            stats.points,
+            0.0,
+            stats.words_found,
+        ),
+    };
+
+    let query = sqlx::query!(
+        "
+            insert into leaderboard_results
+                (discord_id, game, ended_at, won, points, wpm_sum, words_used)
+            values
+                (?, ?, ?, ?, ?, ?, ?)
+        ",
+        discord_id,
+        game,
+        ended_at,
+        won,
+        points,
+        wpm_sum,
+        words_used,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_leaderboard(
+    pool: &SqlitePool,
+    game: &str,
+    weekly: bool,
+    min_games: u32,
+) -> Result<Vec<LeaderboardRow>> {
+    let query = sqlx::query_as!(
+        LeaderboardRow,
+        r#"
+            select
+                users.username,
+                users.avatar_hash,
+                count(*) as "games_played!: i64",
+                sum(leaderboard_results.won) as "wins!: i64",
+                sum(leaderboard_results.points) as "points!: i64",
+                sum(leaderboard_results.wpm_sum) as "wpm_sum!: f64",
+                sum(leaderboard_results.words_used) as "words_used!: i64"
+            from
+                leaderboard_results
+                inner join users using (discord_id)
+            where
+                leaderboard_results.game is ?
+                and leaderboard_results.ended_at >= iif(?, unixepoch('now', '-6 days', 'weekday 1', 'start of day'), 0)
+            group by
+                leaderboard_results.discord_id
+            having
+                count(*) >= ?
+        "#,
+        game,
+        weekly,
+        min_games,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_rating(pool: &SqlitePool, discord_id: &str, game: &str) -> Result<Option<f64>> {
+    let query = sqlx::query_scalar!(
+        "select rating from ratings where discord_id is ? and game is ?",
+        discord_id,
+        game,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_ratings(pool: &SqlitePool, discord_id: &str) -> Result<Vec<Rating>> {
+    let query = sqlx::query_as!(
+        Rating,
+        "select game, rating, games_played from ratings where discord_id is ?",
+        discord_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(query)
+}
+
+// `updated` gets everyone's current rating in the same order as `discord_ids`,
+// the transaction starts with a write so games ending at the same time queue
+// up behind each other instead of overwriting each other's changes
+pub async fn update_ratings(
+    pool: &SqlitePool,
+    discord_ids: &[String],
+    game: &str,
+    starting_rating: f64,
+    updated: impl FnOnce(&[f64]) -> Vec<f64>,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    for discord_id in discord_ids {
+        sqlx::query!(
+            "insert or ignore into ratings (discord_id, game, rating, games_played) values (?, ?, ?, 0)",
+            discord_id,
+            game,
+            starting_rating,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let mut ratings = Vec::with_capacity(discord_ids.len());
+
+    for discord_id in discord_ids {
+        let rating = sqlx::query_scalar!(
+            "select rating from ratings where discord_id is ? and game is ?",
+            discord_id,
+            game,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        ratings.push(rating);
+    }
+
+    for (discord_id, rating) in discord_ids.iter().zip(updated(&ratings)) {
+        sqlx::query!(
+            "update ratings set rating = ?, games_played = games_played + 1 where discord_id is ? and game is ?",
+            rating,
+            discord_id,
+            game,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+// only replaces the stored result if the new one is better
+pub async fn update_personal_best(
+    pool: &SqlitePool,
+    best: &PersonalBestUpdate,
+    achieved_at: u32,
+) -> Result<SqliteQueryResult> {
+    let query = sqlx::query!(
+        "
+            insert into personal_bests (discord_id, game, score, survival_ms, completion, achieved_at)
+            values (?, ?, ?, ?, ?, ?)
+            on conflict (discord_id, game) do update set
+                score = excluded.score,
+                survival_ms = excluded.survival_ms,
+                completion = excluded.completion,
+                achieved_at = excluded.achieved_at
+            where excluded.score > score or (
+                excluded.score = score
+                and (excluded.survival_ms > survival_ms or excluded.completion > completion)
+            )
+        ",
+        best.discord_id,
+        best.game,
+        best.score,
+        best.survival_ms,
+        best.completion,
+        achieved_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(query)
+}
+
+pub async fn get_personal_bests(pool: &SqlitePool, discord_id: &str) -> Result<Vec<PersonalBest>> {
+    let query = sqlx::query_as!(
+        PersonalBest,
+        "
+            select game, score, survival_ms, completion, achieved_at
+            from personal_bests where discord_id is ?
+        ",
+        discord_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(query)
+}
+
+#[derive(Debug)]
+pub struct ListedWord {
+    pub dictionary: String,
+    pub word: String,
+}
+
+pub async fn get_added_words(pool: &SqlitePool) -> Result<Vec<ListedWord>> {
+    let query = sqlx::query_as!(ListedWord, "select dictionary, word from added_words")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(query)
+}
+
+pub async fn get_denied_words(pool: &SqlitePool) -> Result<Vec<ListedWord>> {
+    let query = sqlx::query_as!(ListedWord, "select dictionary, word from denied_words")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(query)
+}
+
+pub async fn add_word(pool: &SqlitePool, dictionary: &str, word: &str) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "delete from denied_words where dictionary is ? and word is ?",
+        dictionary,
+        word,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "insert or ignore into added_words (dictionary, word) values (?, ?)",
+        dictionary,
+        word,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+pub async fn deny_word(pool: &SqlitePool, dictionary: &str, word: &str) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "delete from added_words where dictionary is ? and word is ?",
+        dictionary,
+        word,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "insert or ignore into denied_words (dictionary, word) values (?, ?)",
+        dictionary,
+        word,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
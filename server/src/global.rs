@@ -1,73 +1,384 @@
+use crate::{
+    db,
+    state::games::{anagrams, wordle},
+    utils::UnixTime,
+};
 use rand::{seq::SliceRandom, thread_rng};
-use std::sync::LazyLock;
+use sqlx::SqlitePool;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs, io,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+use thiserror::Error;
+use tokio::{sync::Mutex, task::JoinError};
 
-pub static GLOBAL: LazyLock<GlobalData> = LazyLock::new(GlobalData::new);
+pub static GLOBAL: GlobalData = GlobalData::new();
+
+// games that don't pick a dictionary are played in this one
+pub const DEFAULT_DICTIONARY: &str = "english";
 
 pub struct GlobalData {
-    pub words: Vec<&'static str>,
-    pub prompts: Prompts,
+    // swapped out whole on reloads, games that are using a dictionary keep
+    // their copy until they next look it up
+    dictionaries: RwLock<BTreeMap<String, Arc<Dictionary>>>,
+    // stops two reloads from racing to swap in different versions
+    reloading: Mutex<()>,
+}
+
+#[derive(Error, Debug)]
+#[error("{self:#?}")]
+pub enum DictionaryError {
+    UnknownDictionary,
+    MissingDefault,
+    // missing words some game needs, see `Dictionary::unsupported`
+    Unsupported { name: String, reason: &'static str },
+    Io(#[from] io::Error),
+    Db(#[from] sqlx::Error),
+    Build(#[from] JoinError),
+}
+
+// words admins have added to or denied from a dictionary
+#[derive(Default)]
+struct WordListEdits {
+    added: Vec<String>,
+    denied: HashSet<String>,
 }
 
 impl GlobalData {
-    pub fn new() -> Self {
+    const fn new() -> Self {
         Self {
-            words: include_str!("./static/words_alpha.txt").lines().collect(),
-            prompts: Prompts::new(),
+            dictionaries: RwLock::new(BTreeMap::new()),
+            reloading: Mutex::const_new(()),
         }
     }
 
-    pub fn is_valid(&self, word: &str) -> bool {
-        self.words.binary_search(&word).is_ok()
-    }
+    // every `<name>.txt` in `$DATA_DIR/dictionaries`, one word per line
+    pub async fn reload(&self, pool: &SqlitePool) -> Result<(), DictionaryError> {
+        let _reloading = self.reloading.lock().await;
+        let mut edits = word_list_edits(pool).await?;
 
-    pub fn random_anagram(&self) -> (&str, String) {
-        loop {
-            let anagram = *self.words.choose(&mut thread_rng()).unwrap();
+        let dictionaries = tokio::task::spawn_blocking(move || {
+            let mut dictionaries = BTreeMap::new();
 
-            if anagram.len() == 6 {
-                let mut chars: Vec<char> = anagram.chars().collect();
-                chars.shuffle(&mut thread_rng());
+            for entry in fs::read_dir(dictionaries_dir())? {
+                let path = entry?.path();
 
-                break (anagram, chars.into_iter().collect());
+                if path.extension().and_then(|extension| extension.to_str()) != Some("txt") {
+                    continue;
+                }
+
+                let Some(name) = path.file_stem().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+
+                let edits = edits.remove(name).unwrap_or_default();
+                let dictionary = Dictionary::new(fs::read_to_string(&path)?, &edits);
+
+                if let Some(reason) = dictionary.unsupported() {
+                    if name == DEFAULT_DICTIONARY {
+                        return Err(DictionaryError::Unsupported {
+                            name: name.to_string(),
+                            reason,
+                        });
+                    }
+
+                    eprintln!("skipping dictionary `{name}`: {reason}");
+                    continue;
+                }
+
+                dictionaries.insert(name.to_string(), Arc::new(dictionary));
             }
+
+            Ok::<_, DictionaryError>(dictionaries)
+        })
+        .await??;
+
+        if !dictionaries.contains_key(DEFAULT_DICTIONARY) {
+            return Err(DictionaryError::MissingDefault);
+        }
+
+        *self.dictionaries.write().unwrap() = dictionaries;
+
+        Ok(())
+    }
+
+    // rebuilds one dictionary, after its file or its edits changed
+    pub async fn reload_dictionary(
+        &self,
+        pool: &SqlitePool,
+        name: &str,
+    ) -> Result<Arc<Dictionary>, DictionaryError> {
+        if !self.has_dictionary(name) {
+            return Err(DictionaryError::UnknownDictionary);
+        }
+
+        let _reloading = self.reloading.lock().await;
+        let edits = word_list_edits(pool)
+            .await?
+            .remove(name)
+            .unwrap_or_default();
+        let path = dictionaries_dir().join(format!("{name}.txt"));
+
+        let dictionary = tokio::task::spawn_blocking(move || {
+            Ok::<_, DictionaryError>(Dictionary::new(fs::read_to_string(path)?, &edits))
+        })
+        .await??;
+
+        // the old version stays in use
+        if let Some(reason) = dictionary.unsupported() {
+            return Err(DictionaryError::Unsupported {
+                name: name.to_string(),
+                reason,
+            });
+        }
+
+        let dictionary = Arc::new(dictionary);
+
+        self.dictionaries
+            .write()
+            .unwrap()
+            .insert(name.to_string(), dictionary.clone());
+
+        Ok(dictionary)
+    }
+
+    // falls back to the default for dictionaries that don't exist
+    pub fn dictionary(&self, name: &str) -> Arc<Dictionary> {
+        let dictionaries = self.dictionaries.read().unwrap();
+
+        dictionaries
+            .get(name)
+            .unwrap_or(&dictionaries[DEFAULT_DICTIONARY])
+            .clone()
+    }
+
+    pub fn default_dictionary(&self) -> Arc<Dictionary> {
+        self.dictionary(DEFAULT_DICTIONARY)
+    }
+
+    pub fn has_dictionary(&self, name: &str) -> bool {
+        self.dictionaries.read().unwrap().contains_key(name)
+    }
+
+    pub fn dictionary_names(&self) -> Vec<String> {
+        self.dictionaries.read().unwrap().keys().cloned().collect()
+    }
+}
+
+fn dictionaries_dir() -> PathBuf {
+    let data_dir = dotenvy::var("DATA_DIR").unwrap_or("data".to_string());
+    PathBuf::from(data_dir).join("dictionaries")
+}
+
+async fn word_list_edits(pool: &SqlitePool) -> sqlx::Result<HashMap<String, WordListEdits>> {
+    let mut edits: HashMap<String, WordListEdits> = HashMap::new();
+
+    for db::ListedWord { dictionary, word } in db::get_added_words(pool).await? {
+        edits.entry(dictionary).or_default().added.push(word);
+    }
+
+    for db::ListedWord { dictionary, word } in db::get_denied_words(pool).await? {
+        edits.entry(dictionary).or_default().denied.insert(word);
+    }
+
+    Ok(edits)
+}
+
+// words are shared with the games using them, so they outlive the dictionary
+// when a reload swaps it out mid game
+pub struct Dictionary {
+    pub words: Vec<Arc<str>>,
+    // bitmask of the letters in each word, used to quickly rule out sub words
+    letter_masks: Vec<u32>,
+    // indexes into `words`, grouped by word length
+    lengths: HashMap<usize, Vec<usize>>,
+    pub prompts: Prompts,
+}
+
+impl Dictionary {
+    fn new(words: String, edits: &WordListEdits) -> Self {
+        let mut words: Vec<&str> = words
+            .lines()
+            .map(str::trim)
+            .chain(edits.added.iter().map(String::as_str))
+            .filter(|word| !word.is_empty() && !edits.denied.contains(*word))
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+
+        let words: Vec<Arc<str>> = words.into_iter().map(Arc::from).collect();
+
+        let mut lengths: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for (index, word) in words.iter().enumerate() {
+            lengths.entry(word.len()).or_default().push(index);
+        }
+
+        Self {
+            letter_masks: words.iter().map(|word| letter_mask(word)).collect(),
+            lengths,
+            prompts: Prompts::new(&words),
+            words,
+        }
+    }
+
+    // why a game couldn't be played in this dictionary, games assume every
+    // dictionary that gets loaded can play all of them
+    fn unsupported(&self) -> Option<&'static str> {
+        let mut lengths = anagrams::WORD_LENGTHS.chain([wordle::WORD_LEN]);
+
+        if self.prompts.prompts.is_empty() {
+            Some("no word bomb prompts")
+        } else if !lengths.all(|len| self.lengths.contains_key(&len)) {
+            Some("no words of a length anagrams or wordle needs")
+        } else {
+            None
         }
     }
+
+    pub fn is_valid(&self, word: &str) -> bool {
+        self.words
+            .binary_search_by(|probe| (**probe).cmp(word))
+            .is_ok()
+    }
+
+    // whether any word starts with `prefix` and carries on past it
+    pub fn continues(&self, prefix: &str) -> bool {
+        let start = self.words.partition_point(|word| &**word < prefix);
+
+        self.words[start..]
+            .iter()
+            .take_while(|word| word.starts_with(prefix))
+            .any(|word| word.len() > prefix.len())
+    }
+
+    // (word, its letters shuffled)
+    pub fn random_anagram(&self, len: usize) -> Option<(Arc<str>, String)> {
+        let word = self.random_word(len)?;
+        let mut chars: Vec<char> = word.chars().collect();
+        chars.shuffle(&mut thread_rng());
+
+        Some((word, chars.into_iter().collect()))
+    }
+
+    pub fn random_word(&self, len: usize) -> Option<Arc<str>> {
+        let index = self.lengths.get(&len)?.choose(&mut thread_rng())?;
+
+        Some(self.words[*index].clone())
+    }
+
+    // the same word for everyone until midnight utc
+    pub fn daily_word(&self, len: usize) -> Option<Arc<str>> {
+        let day = SystemTime::now().to_unix_timestamp() as u64 / 86400;
+        let words = self.lengths.get(&len)?;
+
+        // spreads consecutive days across the whole list instead of walking it alphabetically
+        let index = day.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 16;
+
+        Some(self.words[words[index as usize % words.len()]].clone())
+    }
+
+    // every valid word that can be made from `letters`
+    pub fn sub_words<'a>(
+        &'a self,
+        letters: &'a str,
+        min_len: usize,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        let mask = letter_mask(letters);
+
+        self.words
+            .iter()
+            .zip(&self.letter_masks)
+            .filter(move |(word, word_mask)| {
+                (min_len..=letters.len()).contains(&word.len())
+                    && *word_mask & !mask == 0
+                    && word
+                        .chars()
+                        .all(|ch| word.matches(ch).count() <= letters.matches(ch).count())
+            })
+            .map(|(word, _)| &**word)
+    }
+}
+
+fn letter_mask(word: &str) -> u32 {
+    word.bytes()
+        .filter(u8::is_ascii_lowercase)
+        .fold(0, |mask, byte| mask | 1 << (byte - b'a'))
 }
 
 pub struct Prompts {
-    prompts: Vec<&'static str>,
+    // sorted by how many words each prompt is in
+    prompts: Vec<Arc<str>>,
     wpp_indexes: Vec<(usize, usize)>,
 }
 
 impl Prompts {
-    fn new() -> Self {
-        let list = include_str!("./static/prompts.txt")
-            .lines()
-            .filter_map(|line| line.split_once(':'));
+    // prompts are the 2 and 3 letter pieces words split into, scored by how
+    // many words they show up anywhere in
+    fn new(words: &[Arc<str>]) -> Self {
+        // (words the slice is in, whether it's a prompt)
+        let mut counts: HashMap<&str, (usize, bool)> = HashMap::new();
+        let mut word_slices = Vec::new();
 
-        let prompts: Vec<&str> = list
-            .clone()
-            .flat_map(|(_, prompts)| prompts.split(','))
-            .collect();
+        for word in words {
+            let bounds: Vec<usize> = word
+                .char_indices()
+                .map(|(index, _)| index)
+                .chain([word.len()])
+                .collect();
 
-        let wpp_indexes: Vec<(usize, usize)> = list
-            .filter_map(|(wpp, prompts)| wpp.parse().ok().map(|wpp| (wpp, prompts)))
-            .filter_map(|(wpp, line_prompts)| {
-                line_prompts
-                    .split(',')
-                    .next()
-                    .and_then(|first| prompts.iter().position(|&prompt| prompt == first))
-                    .map(|index| (wpp, index))
-            })
+            word_slices.clear();
+
+            for size in 2..=3 {
+                for start in 0..bounds.len().saturating_sub(size) {
+                    let slice = &word[bounds[start]..bounds[start + size]];
+                    word_slices.push((slice, start % size == 0));
+                }
+            }
+
+            // each word only counts once towards a slice
+            word_slices.sort_unstable();
+            word_slices.dedup_by(|(a, a_prompt), (b, b_prompt)| {
+                let duplicate = a == b;
+                *b_prompt |= duplicate && *a_prompt;
+                duplicate
+            });
+
+            for (slice, prompt) in &word_slices {
+                let (count, is_prompt) = counts.entry(slice).or_default();
+                *count += 1;
+                *is_prompt |= prompt;
+            }
+        }
+
+        let mut scored: Vec<(usize, &str)> = counts
+            .into_iter()
+            .filter(|(_, (_, prompt))| *prompt)
+            .map(|(slice, (count, _))| (count, slice))
             .collect();
+        scored.sort_unstable();
+
+        let mut wpp_indexes: Vec<(usize, usize)> = Vec::new();
+
+        for (index, (wpp, _)) in scored.iter().enumerate() {
+            if wpp_indexes.last().map(|(last, _)| last) != Some(wpp) {
+                wpp_indexes.push((*wpp, index));
+            }
+        }
 
         Self {
-            prompts,
+            prompts: scored
+                .into_iter()
+                .map(|(_, prompt)| prompt.into())
+                .collect(),
             wpp_indexes,
         }
     }
 
-    pub fn random_prompt(&self, min_wpp: usize) -> &str {
+    pub fn random_prompt(&self, min_wpp: usize) -> Arc<str> {
         let (_, closest_index) = self
             .wpp_indexes
             .iter()
@@ -77,5 +388,85 @@ impl Prompts {
         self.prompts[*closest_index..]
             .choose(&mut thread_rng())
             .unwrap()
+            .clone()
+    }
+
+    // a prompt with at least `min_wpp` words but fewer than `max_wpp`, for when the
+    // usual uniform pick from `random_prompt` would mostly land on easy ones
+    pub fn random_prompt_between(&self, min_wpp: usize, max_wpp: usize) -> Arc<str> {
+        let index_of = |wpp: usize| {
+            self.wpp_indexes
+                .iter()
+                .find(|(count, _)| *count >= wpp)
+                .map_or(self.prompts.len(), |(_, index)| *index)
+        };
+
+        self.prompts[index_of(min_wpp)..index_of(max_wpp)]
+            .choose(&mut thread_rng())
+            .cloned()
+            .unwrap_or_else(|| self.random_prompt(min_wpp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary(words: &str) -> Dictionary {
+        Dictionary::new(words.to_string(), &WordListEdits::default())
+    }
+
+    #[test]
+    fn edits_are_applied_to_the_word_list() {
+        let edits = WordListEdits {
+            added: vec!["kettle".to_string(), "tea".to_string()],
+            denied: HashSet::from(["teapot".to_string()]),
+        };
+        let dictionary = Dictionary::new(" tea\nteapot\n\nmug\n".to_string(), &edits);
+
+        assert_eq!(
+            dictionary.words,
+            ["kettle", "mug", "tea"].map(Arc::from).to_vec()
+        );
+        assert!(dictionary.is_valid("kettle"));
+        assert!(!dictionary.is_valid("teapot"));
+    }
+
+    #[test]
+    fn words_are_picked_by_length() {
+        let dictionary = dictionary("tea\nmug\nkettle\nteapot\ncup");
+
+        for _ in 0..20 {
+            assert_eq!(dictionary.random_word(3).unwrap().len(), 3);
+            assert_eq!(dictionary.random_anagram(6).unwrap().1.len(), 6);
+        }
+        assert!(dictionary.random_word(4).is_none());
+        assert!(dictionary.daily_word(5).is_none());
+    }
+
+    #[test]
+    fn dictionaries_missing_words_games_need_are_unsupported() {
+        assert_eq!(dictionary("").unsupported(), Some("no word bomb prompts"));
+        assert_eq!(
+            dictionary("tea\nteapot").unsupported(),
+            Some("no words of a length anagrams or wordle needs")
+        );
+
+        let words = ["crane", "teapot", "kettles", "teacakes", "infusions"];
+        assert_eq!(dictionary(&words.join("\n")).unsupported(), None);
+    }
+
+    #[test]
+    fn prompts_are_picked_by_words_per_prompt() {
+        let words = ["tea", "team", "steam", "stem", "seam", "mast"].map(Arc::from);
+        let prompts = Prompts::new(&words);
+        let wpp = |prompt: &str| words.iter().filter(|word| word.contains(prompt)).count();
+
+        for _ in 0..20 {
+            assert!(wpp(&prompts.random_prompt(3)) >= 3);
+            assert_eq!(wpp(&prompts.random_prompt_between(2, 3)), 2);
+        }
+        // nothing is that hard to fit, so it falls back to the closest prompts
+        assert!(wpp(&prompts.random_prompt_between(10, 20)) >= 3);
     }
 }
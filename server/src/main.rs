@@ -7,18 +7,17 @@ mod utils;
 use axum::http::HeaderValue;
 use axum::{routing::get, Router};
 use global::GLOBAL;
-use routes::{auth, game, info};
+use routes::{admin, auth, game, history, info, leaderboard, matchmaking, profile, replay};
 use state::AppState;
 use std::path::Path;
-use std::sync::LazyLock;
 use tower_http::cors::CorsLayer;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    LazyLock::force(&GLOBAL);
     dotenvy::from_path(Path::new("../.env")).ok();
 
     let db = db::create_pool().await?;
+    GLOBAL.reload(&db).await?;
     let state = AppState::new(db);
 
     let mut app = Router::new().nest(
@@ -26,7 +25,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Router::new()
             .nest("/info", info::make_router())
             .nest("/auth", auth::make_router(state.clone()))
+            .nest("/admin", admin::make_router(state.clone()))
+            .nest("/history", history::make_router())
+            .nest("/profile", profile::make_router())
+            .nest("/leaderboard", leaderboard::make_router())
+            .nest("/replay", replay::make_router())
             .route("/room/*room", get(game::ws_handler))
+            .route("/matchmaking", get(matchmaking::matchmaking_handler))
             .with_state(state),
     );
 
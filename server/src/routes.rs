@@ -1,3 +1,9 @@
+pub mod admin;
 pub mod auth;
 pub mod game;
+pub mod history;
 pub mod info;
+pub mod leaderboard;
+pub mod matchmaking;
+pub mod profile;
+pub mod replay;
@@ -0,0 +1,184 @@
+use crate::{
+    db,
+    global::{DictionaryError, GLOBAL},
+    AppState,
+};
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    middleware::{from_fn_with_state, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, post},
+    Json, Router,
+};
+use axum_extra::extract::CookieJar;
+use cookie::Cookie;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub fn make_router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/dictionaries/reload", post(reload))
+        .route("/dictionaries/:dictionary/words", post(add_word))
+        .route("/dictionaries/:dictionary/words/:word", delete(remove_word))
+        .layer(from_fn_with_state(state, check_if_admin))
+}
+
+// admins are the discord ids listed in `ADMIN_IDS`, separated by commas
+async fn check_if_admin(
+    jar: CookieJar,
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let session_id = jar
+        .get("session")
+        .map(Cookie::value)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let user = db::get_user_from_session(&state.db, session_id)
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    let admin_ids = dotenvy::var("ADMIN_IDS").unwrap_or_default();
+
+    if !admin_ids.split(',').any(|id| id.trim() == user.discord_id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(next.run(request).await)
+}
+
+#[derive(Serialize, Debug)]
+struct DictionaryInfo {
+    name: String,
+    words: usize,
+}
+
+#[derive(Deserialize, Debug)]
+struct WordForm {
+    word: String,
+}
+
+// rereads every dictionary file, picking up new ones
+async fn reload(State(state): State<AppState>) -> Result<impl IntoResponse, AdminError> {
+    GLOBAL.reload(&state.db).await?;
+
+    let dictionaries: Vec<DictionaryInfo> = GLOBAL
+        .dictionary_names()
+        .into_iter()
+        .map(|name| DictionaryInfo {
+            words: GLOBAL.dictionary(&name).words.len(),
+            name,
+        })
+        .collect();
+
+    Ok(Json(dictionaries))
+}
+
+async fn add_word(
+    State(state): State<AppState>,
+    Path(dictionary): Path<String>,
+    Json(WordForm { word }): Json<WordForm>,
+) -> Result<impl IntoResponse, AdminError> {
+    let word = clean_word(&word)?;
+    check_dictionary(&dictionary)?;
+
+    db::add_word(&state.db, &dictionary, &word).await?;
+    let words = GLOBAL
+        .reload_dictionary(&state.db, &dictionary)
+        .await?
+        .words
+        .len();
+
+    Ok(Json(DictionaryInfo {
+        name: dictionary,
+        words,
+    }))
+}
+
+// denies the word, so it stays gone even if it's still in the file
+async fn remove_word(
+    State(state): State<AppState>,
+    Path((dictionary, word)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AdminError> {
+    let word = clean_word(&word)?;
+    check_dictionary(&dictionary)?;
+
+    db::deny_word(&state.db, &dictionary, &word).await?;
+    let words = GLOBAL
+        .reload_dictionary(&state.db, &dictionary)
+        .await?
+        .words
+        .len();
+
+    Ok(Json(DictionaryInfo {
+        name: dictionary,
+        words,
+    }))
+}
+
+fn check_dictionary(dictionary: &str) -> Result<(), AdminError> {
+    if GLOBAL.has_dictionary(dictionary) {
+        Ok(())
+    } else {
+        Err(AdminError::UnknownDictionary)
+    }
+}
+
+fn clean_word(word: &str) -> Result<String, AdminError> {
+    let word = word.trim().to_lowercase();
+
+    // letters from any alphabet, so words can be added to dictionaries in other languages
+    if word.is_empty() || !word.chars().all(char::is_alphabetic) {
+        return Err(AdminError::InvalidWord);
+    }
+
+    Ok(word)
+}
+
+#[derive(Serialize, Debug)]
+struct AdminResponse {
+    r#type: &'static str,
+    message: &'static str,
+}
+
+#[derive(Error, Debug)]
+#[error("{self:#?}")]
+pub enum AdminError {
+    UnknownDictionary,
+    InvalidWord,
+    DbError(#[from] sqlx::Error),
+    DictionaryError(#[from] DictionaryError),
+}
+
+impl IntoResponse for AdminError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            AdminError::UnknownDictionary
+            | AdminError::DictionaryError(DictionaryError::UnknownDictionary) => {
+                (StatusCode::NOT_FOUND, "no dictionary with that name")
+            }
+            AdminError::InvalidWord => (StatusCode::BAD_REQUEST, "words can only contain letters"),
+            AdminError::DictionaryError(DictionaryError::MissingDefault) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "the default dictionary's file is missing, kept the old dictionaries",
+            ),
+            AdminError::DictionaryError(DictionaryError::Unsupported { .. }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "the dictionary is missing words some games need, kept the old one",
+            ),
+            error => {
+                eprintln!("admin error: {error:#?}");
+                (StatusCode::INTERNAL_SERVER_ERROR, "something went wrong")
+            }
+        };
+
+        let response = AdminResponse {
+            r#type: "error",
+            message,
+        };
+
+        (status, Json(response)).into_response()
+    }
+}
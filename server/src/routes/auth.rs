@@ -87,7 +87,7 @@ async fn discord_callback(
 ) -> Result<Response, AuthError> {
     let stored_state = jar.get("state").map(Cookie::value);
 
-    if !stored_state.is_some_and(|stored| stored == params.state) {
+    if stored_state != Some(params.state.as_str()) {
         return Err(AuthError::BadRequest);
     }
 
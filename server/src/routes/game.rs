@@ -11,13 +11,21 @@ use axum::{
     response::Response,
 };
 use axum_extra::extract::CookieJar;
-use futures::{stream::StreamExt, SinkExt};
+use futures::{
+    stream::{SplitStream, StreamExt},
+    SinkExt,
+};
 use rustrict::CensorStr;
 use serde::Deserialize;
-use std::borrow::Cow;
-use tokio::sync::mpsc;
+use std::{borrow::Cow, time::Duration};
+use tokio::{sync::mpsc, task::JoinHandle};
 use uuid::Uuid;
 
+// room settings are the biggest thing clients send and grow with every game mode
+const MAX_MESSAGE_LEN: usize = 2000;
+
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(Deserialize, Debug)]
 pub struct Params {
     pub username: String,
@@ -31,32 +39,27 @@ pub async fn ws_handler(
     Path(room): Path<String>,
     Query(params): Query<Params>,
 ) -> Response {
-    if cfg!(debug_assertions) {
-        let user = match jar.get("session") {
-            Some(id) => db::get_user_from_session(&state.db, id.value()).await.ok(),
-            None => None,
-        };
+    let user = session_user(&state, &jar).await;
 
+    if cfg!(debug_assertions) {
         println!(
             "'{}' trying to connect to '{room}' | user: {user:?}",
             params.username,
         );
     }
 
-    let error = if params.username.len() > 12 {
-        Some("username too long (max 12 characters)")
-    } else if params.username.is_empty() {
-        Some("username cannot be empty")
+    let error = if let Some(reason) = username_error(&state, &params.username, &user).await {
+        Some(reason)
     } else if room.len() > 6 {
         Some("invalid room name, must be less than 6 characters")
     } else if !room.chars().all(|c| c.is_ascii_alphanumeric()) {
         Some("invalid room name, must be alphanumeric")
-    } else if params.username.is_inappropriate() {
-        Some("username likely contains innappropriate content")
     } else if room.is_inappropriate() {
         Some("room likely contains innappropriate content")
     } else if state.room_full(&room) {
         Some("room full")
+    } else if user.is_none() && state.room_ranked(&room) {
+        Some("ranked rooms require signing in")
     } else {
         None
     };
@@ -64,11 +67,41 @@ pub async fn ws_handler(
     if let Some(reason) = error {
         ws.on_upgrade(move |socket| send_error(socket, reason))
     } else {
-        ws.on_upgrade(move |socket| handle_socket(socket, state, room, params))
+        ws.on_upgrade(move |socket| handle_socket(socket, state, room, params, user))
+    }
+}
+
+pub async fn session_user(state: &AppState, jar: &CookieJar) -> Option<db::User> {
+    match jar.get("session") {
+        Some(id) => db::get_user_from_session(&state.db, id.value()).await.ok(),
+        None => None,
+    }
+}
+
+pub async fn username_error(
+    state: &AppState,
+    username: &str,
+    user: &Option<db::User>,
+) -> Option<&'static str> {
+    // only the account owner can play under an account's username
+    let reserved = db::get_user_by_username(&state.db, username)
+        .await
+        .is_ok_and(|owner| user.as_ref().map(|user| &user.discord_id) != Some(&owner.discord_id));
+
+    if username.len() > 12 {
+        Some("username too long (max 12 characters)")
+    } else if username.is_empty() {
+        Some("username cannot be empty")
+    } else if reserved {
+        Some("username belongs to a registered account")
+    } else if username.is_inappropriate() {
+        Some("username likely contains innappropriate content")
+    } else {
+        None
     }
 }
 
-async fn send_error(mut socket: WebSocket, reason: &'static str) {
+pub async fn send_error(mut socket: WebSocket, reason: &'static str) {
     socket
         .send(Message::Close(Some(CloseFrame {
             code: close_code::ERROR,
@@ -80,12 +113,18 @@ async fn send_error(mut socket: WebSocket, reason: &'static str) {
     socket.close().await.ok();
 }
 
-async fn handle_socket(socket: WebSocket, state: AppState, room: String, params: Params) {
+async fn handle_socket(
+    socket: WebSocket,
+    state: AppState,
+    room: String,
+    params: Params,
+    user: Option<db::User>,
+) {
     let (mut sender, mut reciever) = socket.split();
     let (proxy, mut inbox) = mpsc::unbounded_channel::<Message>();
 
     let socket_uuid = Uuid::new_v4();
-    let uuid = state.add_client(&room, params, socket_uuid, proxy);
+    let uuid = state.add_client(&room, params, user, socket_uuid, proxy);
     let info = SenderInfo { uuid, room: &room };
 
     let sending_task = tokio::spawn(async move {
@@ -96,23 +135,51 @@ async fn handle_socket(socket: WebSocket, state: AppState, room: String, params:
         }
     });
 
-    while let Some(Ok(msg)) = reciever.next().await {
-        if let Message::Text(text) = msg {
-            if text.len() > 500 {
-                eprintln!("ignoring large message ({} bytes)", text.len());
-                continue;
-            }
+    let ping_task = spawn_ping_task(state.clone(), uuid, room.clone());
 
-            if let Ok(msg) = serde_json::from_str::<ClientMessage>(&text) {
-                state.handle(info, msg);
-            } else {
-                eprintln!("couldnt parse message: {text}");
-            }
-        }
-    }
+    receive_messages(&mut reciever, &state, info).await;
 
+    ping_task.abort();
     sending_task.abort();
     state
         .remove_client(info, socket_uuid)
         .unwrap_or_else(|e| eprintln!("failed to remove client: {e}"));
 }
+
+pub fn spawn_ping_task(state: AppState, uuid: Uuid, room: String) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PING_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            state.ping_client(SenderInfo { uuid, room: &room }).ok();
+        }
+    })
+}
+
+pub async fn receive_messages(
+    reciever: &mut SplitStream<WebSocket>,
+    state: &AppState,
+    info: SenderInfo<'_>,
+) {
+    while let Some(Ok(msg)) = reciever.next().await {
+        match msg {
+            Message::Text(text) => {
+                if text.len() > MAX_MESSAGE_LEN {
+                    eprintln!("ignoring large message ({} bytes)", text.len());
+                    continue;
+                }
+
+                if let Ok(msg) = serde_json::from_str::<ClientMessage>(&text) {
+                    state.handle(info, msg);
+                } else {
+                    eprintln!("couldnt parse message: {text}");
+                }
+            }
+            Message::Pong(payload) => {
+                state.client_pong(info, &payload).ok();
+            }
+            _ => {}
+        }
+    }
+}
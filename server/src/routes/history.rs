@@ -0,0 +1,140 @@
+use crate::{db, AppState};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use axum_extra::extract::CookieJar;
+use serde::{Deserialize, Serialize};
+
+const PAGE_SIZE: u32 = 20;
+
+pub fn make_router() -> Router<AppState> {
+    Router::new()
+        .route("/me", get(user_history))
+        .route("/room/:room", get(room_history))
+        .route("/game/:game_id", get(game_details))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Page {
+    #[serde(default)]
+    page: u32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GameSummary {
+    pub id: i64,
+    pub room: String,
+    pub game: String,
+    pub started_at: i64,
+    pub duration_ms: i64,
+    pub players: Vec<db::GamePlayer>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GameDetails {
+    pub id: i64,
+    pub room: String,
+    pub game: String,
+    pub settings: serde_json::Value,
+    pub started_at: i64,
+    pub duration_ms: i64,
+    pub players: Vec<db::GamePlayer>,
+    pub words: Vec<db::GameWord>,
+}
+
+async fn user_history(
+    jar: CookieJar,
+    State(state): State<AppState>,
+    Query(Page { page }): Query<Page>,
+) -> Result<Json<Vec<GameSummary>>, StatusCode> {
+    let session_id = jar.get("session").ok_or(StatusCode::UNAUTHORIZED)?.value();
+
+    let user = db::get_user_from_session(&state.db, session_id)
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    let rows = db::get_user_history(
+        &state.db,
+        &user.discord_id,
+        PAGE_SIZE,
+        page.saturating_mul(PAGE_SIZE),
+    )
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(group_history(rows)))
+}
+
+async fn room_history(
+    State(state): State<AppState>,
+    Path(room): Path<String>,
+    Query(Page { page }): Query<Page>,
+) -> Result<Json<Vec<GameSummary>>, StatusCode> {
+    let rows = db::get_room_history(&state.db, &room, PAGE_SIZE, page.saturating_mul(PAGE_SIZE))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(group_history(rows)))
+}
+
+async fn game_details(
+    State(state): State<AppState>,
+    Path(game_id): Path<i64>,
+) -> Result<Json<GameDetails>, StatusCode> {
+    let game = db::get_game(&state.db, game_id)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        })?;
+
+    let players = db::get_game_players(&state.db, game_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let words = db::get_game_words(&state.db, game_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(GameDetails {
+        id: game.game_id,
+        room: game.room,
+        game: game.game,
+        settings: serde_json::from_str(&game.settings).unwrap_or_default(),
+        started_at: game.started_at,
+        duration_ms: game.duration_ms,
+        players,
+        words,
+    }))
+}
+
+// rows come back ordered by game, one per player
+fn group_history(rows: Vec<db::HistoryRow>) -> Vec<GameSummary> {
+    let mut games: Vec<GameSummary> = Vec::new();
+
+    for row in rows {
+        let player = db::GamePlayer {
+            uuid: row.uuid,
+            username: row.username,
+            account: row.account,
+            placement: row.placement,
+        };
+
+        match games.last_mut() {
+            Some(game) if game.id == row.game_id => game.players.push(player),
+            _ => games.push(GameSummary {
+                id: row.game_id,
+                room: row.room,
+                game: row.game,
+                started_at: row.started_at,
+                duration_ms: row.duration_ms,
+                players: vec![player],
+            }),
+        }
+    }
+
+    games
+}
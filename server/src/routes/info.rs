@@ -1,4 +1,4 @@
-use crate::{state::messages::Games, AppState};
+use crate::{global::GLOBAL, state::messages::Games, AppState};
 use axum::{
     extract::{Path, State},
     routing::get,
@@ -24,6 +24,7 @@ async fn room_available(State(state): State<AppState>, Path(room_name): Path<Str
 pub struct ServerInfo {
     pub clients_connected: usize,
     pub public_rooms: Vec<RoomData>,
+    pub dictionaries: Vec<String>,
 }
 
 #[derive(Serialize, Debug)]
@@ -51,6 +52,7 @@ impl AppState {
                     game: room.settings.game,
                 })
                 .collect(),
+            dictionaries: GLOBAL.dictionary_names(),
         }
     }
 
@@ -0,0 +1,123 @@
+use crate::{db, state::messages::Games, AppState};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+const ENTRIES: usize = 50;
+
+pub fn make_router() -> Router<AppState> {
+    Router::new().route("/:game", get(leaderboard))
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Weekly,
+    #[default]
+    All,
+}
+
+impl Period {
+    fn min_games(self) -> u32 {
+        match self {
+            Period::Weekly => 3,
+            Period::All => 10,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Params {
+    #[serde(default)]
+    period: Period,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Leaderboard {
+    pub game: Games,
+    pub period: Period,
+    pub min_games: u32,
+    pub rankings: Rankings,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "type")]
+pub enum Rankings {
+    WordBomb {
+        wins: Vec<Entry>,
+        avg_wpm: Vec<Entry>,
+    },
+    Anagrams {
+        wins: Vec<Entry>,
+        points: Vec<Entry>,
+    },
+}
+
+#[derive(Serialize, Debug)]
+pub struct Entry {
+    pub username: String,
+    pub avatar_hash: String,
+    pub games_played: i64,
+    pub value: f64,
+}
+
+async fn leaderboard(
+    State(state): State<AppState>,
+    Path(game): Path<Games>,
+    Query(Params { period }): Query<Params>,
+) -> Result<Json<Leaderboard>, StatusCode> {
+    let rows = db::get_leaderboard(
+        &state.db,
+        game.as_str(),
+        matches!(period, Period::Weekly),
+        period.min_games(),
+    )
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let wins = rank(&rows, |row| row.wins as f64);
+
+    let rankings = match game {
+        Games::WordBomb => Rankings::WordBomb {
+            wins,
+            avg_wpm: rank(&rows, |row| row.wpm_sum / row.words_used.max(1) as f64),
+        },
+        Games::Anagrams => Rankings::Anagrams {
+            wins,
+            points: rank(&rows, |row| row.points as f64),
+        },
+        // the other games don't keep leaderboards
+        Games::Greentea | Games::WordChain | Games::Wordle | Games::Boggle => {
+            return Err(StatusCode::NOT_FOUND)
+        }
+    };
+
+    Ok(Json(Leaderboard {
+        game,
+        period,
+        min_games: period.min_games(),
+        rankings,
+    }))
+}
+
+fn rank(rows: &[db::LeaderboardRow], value: impl Fn(&db::LeaderboardRow) -> f64) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = rows
+        .iter()
+        .map(|row| Entry {
+            username: row.username.clone(),
+            avatar_hash: row.avatar_hash.clone(),
+            games_played: row.games_played,
+            value: value(row),
+        })
+        .collect();
+
+    entries.sort_by(|a, b| b.value.partial_cmp(&a.value).unwrap_or(Ordering::Equal));
+    entries.truncate(ENTRIES);
+
+    entries
+}
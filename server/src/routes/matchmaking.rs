@@ -0,0 +1,127 @@
+use crate::{
+    db,
+    routes::game::{
+        receive_messages, send_error, session_user, spawn_ping_task, username_error, Params,
+    },
+    state::{
+        matchmaking::{QueuedPlayer, QUEUE_TIMEOUT},
+        messages::Games,
+        rating, SenderInfo,
+    },
+    AppState,
+};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket},
+        Query, State, WebSocketUpgrade,
+    },
+    response::Response,
+};
+use axum_extra::extract::CookieJar;
+use futures::{stream::StreamExt, SinkExt};
+use serde::Deserialize;
+use std::time::Instant;
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+#[derive(Deserialize, Debug)]
+pub struct MatchmakingParams {
+    pub username: String,
+    pub game: Games,
+}
+
+pub async fn matchmaking_handler(
+    ws: WebSocketUpgrade,
+    jar: CookieJar,
+    State(state): State<AppState>,
+    Query(params): Query<MatchmakingParams>,
+) -> Response {
+    let user = session_user(&state, &jar).await;
+
+    if let Some(reason) = username_error(&state, &params.username, &user).await {
+        ws.on_upgrade(move |socket| send_error(socket, reason))
+    } else {
+        ws.on_upgrade(move |socket| handle_matchmaking(socket, state, params, user))
+    }
+}
+
+async fn handle_matchmaking(
+    socket: WebSocket,
+    state: AppState,
+    params: MatchmakingParams,
+    user: Option<db::User>,
+) {
+    let (mut sender, mut reciever) = socket.split();
+    let (proxy, mut inbox) = mpsc::unbounded_channel::<Message>();
+
+    let socket_uuid = Uuid::new_v4();
+
+    let sending_task = tokio::spawn(async move {
+        while let Some(msg) = inbox.recv().await {
+            sender.send(msg).await.unwrap_or_else(|e| {
+                eprintln!("ws send error for {socket_uuid}: {e}");
+            });
+        }
+    });
+
+    let rating = match &user {
+        Some(user) => db::get_rating(&state.db, &user.discord_id, params.game.as_str())
+            .await
+            .ok()
+            .flatten(),
+        None => None,
+    };
+
+    let (matched, mut on_match) = oneshot::channel();
+
+    state.join_queue(QueuedPlayer {
+        game: params.game,
+        rating: rating.unwrap_or(rating::STARTING_RATING),
+        joined: Instant::now(),
+        params: Params {
+            username: params.username,
+            rejoin_token: None,
+        },
+        user,
+        socket: socket_uuid,
+        tx: proxy,
+        matched,
+    });
+
+    let timeout_state = state.clone();
+    let timeout_task = tokio::spawn(async move {
+        tokio::time::sleep(QUEUE_TIMEOUT).await;
+
+        timeout_state
+            .check_queue(params.game)
+            .unwrap_or_else(|e| eprintln!("failed to create match: {e}"));
+    });
+
+    // messages sent while queued are ignored, the socket is only watched for closing
+    let matched = loop {
+        tokio::select! {
+            result = &mut on_match => break result.ok(),
+            msg = reciever.next() => match msg {
+                Some(Ok(_)) => continue,
+                _ if state.leave_queue(socket_uuid) => break None,
+                _ => break (&mut on_match).await.ok(),
+            },
+        }
+    };
+
+    timeout_task.abort();
+
+    if let Some((room, uuid)) = matched {
+        let info = SenderInfo { uuid, room: &room };
+        let ping_task = spawn_ping_task(state.clone(), uuid, room.clone());
+
+        receive_messages(&mut reciever, &state, info).await;
+
+        ping_task.abort();
+        state
+            .remove_client(info, socket_uuid)
+            .unwrap_or_else(|e| eprintln!("failed to remove client: {e}"));
+    }
+
+    sending_task.abort();
+}
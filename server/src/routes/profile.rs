@@ -0,0 +1,136 @@
+use crate::{db, AppState};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+
+pub fn make_router() -> Router<AppState> {
+    Router::new().route("/:username", get(profile))
+}
+
+#[derive(Serialize, Debug)]
+pub struct Profile {
+    pub username: String,
+    pub avatar_hash: String,
+    pub games_played: i64,
+    pub wins: i64,
+    pub win_rate: f64,
+    pub best_wpm: Option<f64>,
+    pub longest_word: Option<String>,
+    pub ratings: Vec<db::Rating>,
+    pub personal_bests: Vec<db::PersonalBest>,
+    pub word_bomb: Option<WordBombProfile>,
+    pub anagrams: Option<AnagramsProfile>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WordBombProfile {
+    pub games_played: i64,
+    pub wins: i64,
+    pub win_rate: f64,
+    pub words_used: i64,
+    pub avg_wpm: f64,
+    pub best_wpm: f64,
+    pub avg_word_length: f64,
+    pub fastest_guess_ms: Option<i64>,
+    pub longest_word: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AnagramsProfile {
+    pub games_played: i64,
+    pub wins: i64,
+    pub win_rate: f64,
+    pub points: i64,
+    pub avg_points: f64,
+    pub best_points: i64,
+    pub words_found: i64,
+    pub longest_word: String,
+}
+
+async fn profile(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> Result<Json<Profile>, StatusCode> {
+    let user = db::get_user_by_username(&state.db, &username)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        })?;
+
+    let word_bomb = db::get_word_bomb_stats(&state.db, &user.discord_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map(|stats| WordBombProfile {
+            games_played: stats.games_played,
+            wins: stats.wins,
+            win_rate: ratio(stats.wins, stats.games_played),
+            words_used: stats.words_used,
+            avg_wpm: stats.wpm_sum / stats.words_used.max(1) as f64,
+            best_wpm: stats.best_wpm,
+            avg_word_length: ratio(stats.letters_used, stats.words_used),
+            fastest_guess_ms: stats.fastest_guess_ms,
+            longest_word: stats.longest_word,
+        });
+
+    let anagrams = db::get_anagrams_stats(&state.db, &user.discord_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map(|stats| AnagramsProfile {
+            games_played: stats.games_played,
+            wins: stats.wins,
+            win_rate: ratio(stats.wins, stats.games_played),
+            points: stats.points,
+            avg_points: ratio(stats.points, stats.games_played),
+            best_points: stats.best_points,
+            words_found: stats.words_found,
+            longest_word: stats.longest_word,
+        });
+
+    let ratings = db::get_ratings(&state.db, &user.discord_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let personal_bests = db::get_personal_bests(&state.db, &user.discord_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let games_played = word_bomb.as_ref().map_or(0, |stats| stats.games_played)
+        + anagrams.as_ref().map_or(0, |stats| stats.games_played);
+    let wins = word_bomb.as_ref().map_or(0, |stats| stats.wins)
+        + anagrams.as_ref().map_or(0, |stats| stats.wins);
+
+    let longest_word = word_bomb
+        .iter()
+        .map(|stats| &stats.longest_word)
+        .chain(anagrams.iter().map(|stats| &stats.longest_word))
+        .max_by_key(|word| word.len())
+        .filter(|word| !word.is_empty())
+        .cloned();
+
+    Ok(Json(Profile {
+        username: user.username,
+        avatar_hash: user.avatar_hash,
+        games_played,
+        wins,
+        win_rate: ratio(wins, games_played),
+        best_wpm: word_bomb.as_ref().map(|stats| stats.best_wpm),
+        longest_word,
+        ratings,
+        personal_bests,
+        word_bomb,
+        anagrams,
+    }))
+}
+
+fn ratio(part: i64, total: i64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
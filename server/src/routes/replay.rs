@@ -0,0 +1,33 @@
+use crate::{db, AppState};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+
+pub fn make_router() -> Router<AppState> {
+    Router::new().route("/:game_id", get(replay))
+}
+
+// replays are stored gzipped, so they're sent as is
+async fn replay(
+    State(state): State<AppState>,
+    Path(game_id): Path<i64>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let data = db::get_replay(&state.db, game_id)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json"),
+            (header::CONTENT_ENCODING, "gzip"),
+        ],
+        data,
+    ))
+}
@@ -1,7 +1,12 @@
+pub mod bot;
 pub mod error;
 pub mod games;
+pub mod history;
 pub mod lobby;
+pub mod matchmaking;
 pub mod messages;
+pub mod rating;
+pub mod replay;
 pub mod room;
 
 use dashmap::{
@@ -10,10 +15,14 @@ use dashmap::{
 };
 use error::{GameError, Result};
 use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+use matchmaking::QueuedPlayer;
 use messages::ClientMessage;
 use room::Room;
 use sqlx::SqlitePool;
-use std::{num::NonZeroU32, sync::Arc};
+use std::{
+    num::NonZeroU32,
+    sync::{Arc, Mutex},
+};
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -21,6 +30,7 @@ pub struct AppState {
     pub db: SqlitePool,
     pub rooms: Arc<DashMap<String, Room>>,
     pub limiter: Arc<DefaultKeyedRateLimiter<Uuid>>,
+    pub queue: Arc<Mutex<Vec<QueuedPlayer>>>,
 }
 
 #[derive(Clone, Copy)]
@@ -39,16 +49,17 @@ impl AppState {
                 Quota::per_second(NonZeroU32::new(8).unwrap())
                     .allow_burst(NonZeroU32::new(24).unwrap()),
             )),
+            queue: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
-    pub fn room(&self, room: &str) -> Result<Ref<String, Room>> {
+    pub fn room(&self, room: &str) -> Result<Ref<'_, String, Room>> {
         self.rooms.get(room).ok_or(GameError::RoomNotFound {
             room: room.to_string(),
         })
     }
 
-    pub fn room_mut(&self, room: &str) -> Result<RefMut<String, Room>> {
+    pub fn room_mut(&self, room: &str) -> Result<RefMut<'_, String, Room>> {
         self.rooms.get_mut(room).ok_or(GameError::RoomNotFound {
             room: room.to_string(),
         })
@@ -75,10 +86,13 @@ impl AppState {
                 ClientMessage::RoomSettings(settings) => {
                     self.client_room_settings(sender, settings)
                 }
+                ClientMessage::AddBot { difficulty } => self.client_add_bot(sender, difficulty),
+                ClientMessage::RemoveBot { uuid } => self.client_remove_bot(sender, uuid),
                 ClientMessage::ChatMessage { content } => self.client_chat_message(sender, content),
-                ClientMessage::WordBombInput { input } => self.word_bomb_input(sender, input),
-                ClientMessage::WordBombGuess { word } => self.word_bomb_guess(sender, word),
-                ClientMessage::AnagramsGuess { word } => self.anagrams_guess(sender, word),
+                // anything else is up to the game being played
+                message => self.update_game(sender.room, |game, ctx| {
+                    game.handle(ctx, sender.uuid, message)
+                }),
             });
 
         if let Err(error) = result {
@@ -0,0 +1,323 @@
+use crate::{
+    global::GLOBAL,
+    state::{
+        error::{Result, RoomError},
+        games::word_bomb::{Variant, WordBomb},
+        lobby::check_for_countdown_update,
+        messages::{ClientMessage, ConnectionUpdate, Games, ServerMessage},
+        room::Client,
+        Room, SenderInfo,
+    },
+    utils::ClientUtils,
+    AppState,
+};
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum BotDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl BotDifficulty {
+    fn username(self) -> &'static str {
+        match self {
+            BotDifficulty::Easy => "easy bot",
+            BotDifficulty::Medium => "medium bot",
+            BotDifficulty::Hard => "hard bot",
+        }
+    }
+
+    // before starting to type
+    fn think_time(self) -> Duration {
+        random_duration(match self {
+            BotDifficulty::Easy => 1500..=3500,
+            BotDifficulty::Medium => 800..=2000,
+            BotDifficulty::Hard => 300..=900,
+        })
+    }
+
+    // per letter typed
+    fn keystroke_time(self) -> Duration {
+        random_duration(match self {
+            BotDifficulty::Easy => 180..=320,
+            BotDifficulty::Medium => 110..=200,
+            BotDifficulty::Hard => 60..=110,
+        })
+    }
+
+    fn max_word_len(self) -> usize {
+        match self {
+            BotDifficulty::Easy => 7,
+            BotDifficulty::Medium => 10,
+            BotDifficulty::Hard => 35,
+        }
+    }
+
+    // chance of blanking on a prompt and letting the bomb go off
+    fn fail_chance(self) -> f64 {
+        match self {
+            BotDifficulty::Easy => 0.25,
+            BotDifficulty::Medium => 0.12,
+            BotDifficulty::Hard => 0.04,
+        }
+    }
+}
+
+impl AppState {
+    pub fn client_add_bot(
+        &self,
+        SenderInfo { uuid, room }: SenderInfo,
+        difficulty: BotDifficulty,
+    ) -> Result<()> {
+        let mut lock = self.room_mut(room)?;
+        let Room {
+            owner,
+            settings,
+            clients,
+            state,
+            ..
+        } = lock.value_mut();
+        let lobby = state.try_lobby()?;
+
+        if *owner != uuid || clients.len() >= 8 {
+            return Ok(());
+        }
+
+        if settings.game != Games::WordBomb || settings.ranked {
+            return Err(RoomError::BotsUnsupported)?;
+        }
+
+        let bot = Uuid::new_v4();
+        // nothing listens to a bot's messages
+        let (tx, _) = mpsc::unbounded_channel();
+
+        let client = Client {
+            socket: Some(Uuid::new_v4()),
+            tx,
+            username: difficulty.username().to_string(),
+            user: None,
+            rejoin_token: None,
+            latency: Duration::ZERO,
+            pending_ping: None,
+            bot: Some(difficulty),
+        };
+
+        clients.broadcast(ServerMessage::ConnectionUpdate {
+            uuid: bot,
+            state: ConnectionUpdate::Connected {
+                username: client.username.clone(),
+                account: None,
+                verified: false,
+                bot: true,
+            },
+        });
+
+        clients.insert(bot, client);
+        lobby.ready.insert(bot);
+
+        let countdown_update =
+            check_for_countdown_update(self.clone(), room.to_string(), lobby, clients);
+
+        clients.broadcast(ServerMessage::ReadyPlayers {
+            ready: lobby.ready.iter().copied().collect(),
+            countdown_update,
+        });
+
+        Ok(())
+    }
+
+    pub fn client_remove_bot(
+        &self,
+        SenderInfo { uuid, room }: SenderInfo,
+        bot: Uuid,
+    ) -> Result<()> {
+        let mut lock = self.room_mut(room)?;
+        let Room {
+            owner,
+            clients,
+            state,
+            ..
+        } = lock.value_mut();
+        let lobby = state.try_lobby()?;
+
+        let is_bot = clients.get(&bot).is_some_and(|client| client.bot.is_some());
+
+        if *owner != uuid || !is_bot {
+            return Ok(());
+        }
+
+        clients.remove(&bot);
+        lobby.ready.remove(&bot);
+
+        let countdown_update =
+            check_for_countdown_update(self.clone(), room.to_string(), lobby, clients);
+
+        clients.broadcast(ServerMessage::ReadyPlayers {
+            ready: lobby.ready.iter().copied().collect(),
+            countdown_update,
+        });
+
+        clients.broadcast(ServerMessage::ConnectionUpdate {
+            uuid: bot,
+            state: ConnectionUpdate::Disconnected {
+                new_room_owner: None,
+            },
+        });
+
+        Ok(())
+    }
+
+    async fn bot_turn(
+        &self,
+        room: String,
+        bot: Uuid,
+        turn_start: Instant,
+        difficulty: BotDifficulty,
+    ) -> Result<()> {
+        tokio::time::sleep(difficulty.think_time()).await;
+
+        // only what the search needs is copied out, so the room isn't locked while it runs
+        let turn = {
+            let mut lock = self.room_mut(&room)?;
+            let game = lock.state.try_game::<WordBomb>()?;
+
+            if game.turn != bot || game.timer.start != turn_start {
+                return Ok(());
+            }
+
+            BotTurn::new(game)
+        };
+
+        // the typing below checks the turn is still the bot's before each keystroke
+        let Some(word) = turn.pick_word(difficulty) else {
+            return Ok(());
+        };
+
+        // bots that fail still type a bit before giving up
+        let letters = word.chars().count();
+        let typed = if thread_rng().gen_bool(difficulty.fail_chance()) {
+            thread_rng().gen_range(0..letters)
+        } else {
+            letters
+        };
+
+        for len in 1..=typed {
+            tokio::time::sleep(difficulty.keystroke_time()).await;
+
+            if !self.bot_turn_active(&room, bot, turn_start) {
+                return Ok(());
+            }
+
+            let input = word.chars().take(len).collect();
+            self.update_game(&room, |game, ctx| {
+                game.handle(ctx, bot, ClientMessage::WordBombInput { input })
+            })?;
+        }
+
+        if typed == letters && self.bot_turn_active(&room, bot, turn_start) {
+            let word = word.to_string();
+            self.update_game(&room, |game, ctx| {
+                game.handle(ctx, bot, ClientMessage::WordBombGuess { word })
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn bot_turn_active(&self, room: &str, bot: Uuid, turn_start: Instant) -> bool {
+        self.room(room).is_ok_and(|lock| {
+            lock.state
+                .game()
+                .and_then(|game| game.as_any().downcast_ref::<WordBomb>())
+                .is_some_and(|game| game.turn == bot && game.timer.start == turn_start)
+        })
+    }
+}
+
+fn random_duration(millis: RangeInclusive<u64>) -> Duration {
+    Duration::from_millis(thread_rng().gen_range(millis))
+}
+
+struct BotTurn {
+    dictionary: String,
+    prompt: Arc<str>,
+    variant: Variant,
+    last_word_len: usize,
+    // words used this game, plus earlier games in no repeats
+    used_words: HashSet<String>,
+}
+
+impl BotTurn {
+    fn new(game: &WordBomb) -> Self {
+        let mut used_words: HashSet<String> = game
+            .players
+            .iter()
+            .flat_map(|player| player.used_words.iter().map(|(_, word)| word.clone()))
+            .collect();
+
+        if game.settings.variant == Variant::NoRepeats {
+            used_words.extend(game.session_words.iter().cloned());
+        }
+
+        Self {
+            dictionary: game.dictionary.clone(),
+            prompt: game.prompt.clone(),
+            variant: game.settings.variant,
+            last_word_len: game.last_word_len,
+            used_words,
+        }
+    }
+
+    // walks the dictionary from a random word and stops at the first that fits
+    fn pick_word(&self, difficulty: BotDifficulty) -> Option<Arc<str>> {
+        let dictionary = GLOBAL.dictionary(&self.dictionary);
+        let words = &dictionary.words;
+        let start = thread_rng().gen_range(0..words.len().max(1));
+
+        words[start..]
+            .iter()
+            .chain(&words[..start])
+            .find(|word| {
+                word.len() <= difficulty.max_word_len()
+                    && (self.variant != Variant::Hardcore || word.len() > self.last_word_len)
+                    && word.contains(&*self.prompt)
+                    && !self.used_words.contains(&***word)
+            })
+            .cloned()
+    }
+}
+
+// starts the current player's turn if they're a bot
+pub fn check_for_bot_turn(
+    app_state: &AppState,
+    room: &str,
+    clients: &HashMap<Uuid, Client>,
+    game: &WordBomb,
+) {
+    let Some(difficulty) = clients.get(&game.turn).and_then(|client| client.bot) else {
+        return;
+    };
+
+    let app_state = app_state.clone();
+    let room = room.to_string();
+    let bot = game.turn;
+    let turn_start = game.timer.start;
+
+    tokio::spawn(async move {
+        app_state
+            .bot_turn(room, bot, turn_start, difficulty)
+            .await
+            .inspect_err(|error| eprintln!("bot turn error: {error:#?}"))
+    });
+}
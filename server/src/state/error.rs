@@ -10,22 +10,40 @@ pub enum GameError {
     InvalidState { state: &'static str },
     #[error("rate limited, you're sending messages too fast")]
     RateLimited,
+    #[error("message wasn't meant for the game being played")]
+    UnexpectedMessage,
     #[error(transparent)]
     Room(#[from] RoomError),
     #[error(transparent)]
     WordBomb(#[from] WordBombError),
     #[error(transparent)]
     Anagrams(#[from] AnagramsError),
+    #[error(transparent)]
+    Greentea(#[from] GreenteaError),
+    #[error(transparent)]
+    WordChain(#[from] WordChainError),
+    #[error(transparent)]
+    Wordle(#[from] WordleError),
+    #[error(transparent)]
+    Boggle(#[from] BoggleError),
 }
 
 #[derive(Error, Debug)]
 pub enum RoomError {
     #[error("chat message was too long")]
     ChatMessageTooLong,
+    #[error("client couldn't be found")]
+    ClientNotFound,
     #[error("client couldn't be found while removing")]
     CouldntFindClientToRemove,
     #[error("client's socket uuids did not match up while removing")]
     SocketUuidMismatchWhileRemoving,
+    #[error("room can't be ranked while players aren't signed in")]
+    GuestsInRankedRoom,
+    #[error("bots can only play unranked word bomb")]
+    BotsUnsupported,
+    #[error("dictionary `{name}` doesn't exist")]
+    UnknownDictionary { name: String },
 }
 
 #[derive(Error, Debug)]
@@ -34,6 +52,10 @@ pub enum AnagramsError {
     GuessTooLong,
     #[error("spectator(?) tried playing")]
     PlayerNotFound,
+    #[error("guess was made between rounds")]
+    BetweenRounds,
+    #[error("invalid anagrams settings: {reason}")]
+    InvalidSettings { reason: &'static str },
 }
 
 #[derive(Error, Debug)]
@@ -48,4 +70,62 @@ pub enum WordBombError {
     OutOfTurn,
     #[error("can't update turn because nobody/nobody else is alive")]
     NoPlayersAlive,
+    #[error("client tried using a power up outside of a power up game")]
+    PowerUpsDisabled,
+    #[error("client tried using a power up they don't have")]
+    PowerUpNotOwned,
+    #[error("invalid word bomb settings: {reason}")]
+    InvalidSettings { reason: &'static str },
+}
+
+#[derive(Error, Debug)]
+pub enum GreenteaError {
+    #[error("player's guess was too long")]
+    GuessTooLong,
+    #[error("spectator(?) tried playing")]
+    PlayerNotFound,
+    #[error("guess was made between rounds")]
+    BetweenRounds,
+    #[error("player already submitted a word this round")]
+    AlreadySubmitted,
+    #[error("invalid greentea settings: {reason}")]
+    InvalidSettings { reason: &'static str },
+}
+
+#[derive(Error, Debug)]
+pub enum WordChainError {
+    #[error("player's input was too long")]
+    InputTooLong,
+    #[error("player's guess was too long")]
+    GuessTooLong,
+    #[error("spectator(?) tried playing")]
+    PlayerNotFound,
+    #[error("client tried playing out of turn")]
+    OutOfTurn,
+    #[error("can't update turn because nobody/nobody else is alive")]
+    NoPlayersAlive,
+    #[error("invalid word chain settings: {reason}")]
+    InvalidSettings { reason: &'static str },
+}
+
+#[derive(Error, Debug)]
+pub enum WordleError {
+    #[error("player's guess was too long")]
+    GuessTooLong,
+    #[error("spectator(?) tried playing")]
+    PlayerNotFound,
+    #[error("player already solved the word or ran out of guesses")]
+    AlreadyFinished,
+    #[error("invalid wordle settings: {reason}")]
+    InvalidSettings { reason: &'static str },
+}
+
+#[derive(Error, Debug)]
+pub enum BoggleError {
+    #[error("player's guess was too long")]
+    GuessTooLong,
+    #[error("spectator(?) tried playing")]
+    PlayerNotFound,
+    #[error("invalid boggle settings: {reason}")]
+    InvalidSettings { reason: &'static str },
 }
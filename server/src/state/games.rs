@@ -1,2 +1,197 @@
 pub mod anagrams;
+pub mod boggle;
+pub mod greentea;
+// what word bomb and word chain share, both pass a turn around the players
+// against a timer that costs a life when it runs out
+pub mod turns;
 pub mod word_bomb;
+pub mod word_chain;
+pub mod wordle;
+
+use crate::{
+    state::{
+        error::{GameError, Result},
+        history::GameRecord,
+        lobby::end_game,
+        messages::{ClientMessage, PostGameInfo, RoomStateInfo},
+        replay::Replay,
+        room::{Client, RoomSettings, State},
+        Room,
+    },
+    AppState,
+};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+// a game being played in a room, the room looks after its clients and goes
+// back to the lobby once the game says it's over
+pub trait Game: AsAny + Debug + Send + Sync {
+    fn name() -> &'static str
+    where
+        Self: Sized;
+
+    // sets up a game between the lobby's ready players
+    fn start(ctx: Context, players: &HashSet<Uuid>, settings: &RoomSettings) -> Self
+    where
+        Self: Sized;
+
+    fn handle(&mut self, ctx: Context, uuid: Uuid, message: ClientMessage) -> Result<Flow>;
+
+    // the game as `uuid` sees it, or as a spectator would
+    fn snapshot(&self, uuid: Option<Uuid>) -> RoomStateInfo;
+
+    // the client can still rejoin with their rejoin token
+    fn on_disconnect(&mut self, _ctx: Context, _uuid: Uuid) -> Result<Flow> {
+        Ok(Flow::Continue)
+    }
+
+    // how long a timer that ran out waits for messages that are still in flight
+    fn grace(&self, _ctx: Context) -> Duration {
+        Duration::ZERO
+    }
+
+    // `started` is when the timer was spawned, to tell it apart from ones
+    // that were replaced after it had already woken up
+    fn on_timer(&mut self, ctx: Context, started: Instant) -> Result<Flow>;
+
+    fn post_game_info(&mut self) -> (PostGameInfo, GameRecord);
+
+    fn replay(&mut self) -> &mut Replay;
+
+    // stops the game's timers, for when the room closes mid game
+    fn abort(&self);
+}
+
+pub enum Flow {
+    Continue,
+    End,
+}
+
+// lets a specific game be taken back out of a `dyn Game`
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// the parts of a room a game can use while the room is locked
+#[derive(Clone, Copy)]
+pub struct Context<'a> {
+    pub app_state: &'a AppState,
+    pub room: &'a str,
+    pub clients: &'a HashMap<Uuid, Client>,
+}
+
+impl Context<'_> {
+    // calls `Game::on_timer` after `length`, returns the task and when it was spawned
+    pub fn spawn_timer(&self, length: Duration) -> (Arc<AbortHandle>, Instant) {
+        let app_state = self.app_state.clone();
+        let room = self.room.to_string();
+        let started = Instant::now();
+
+        let task = tokio::spawn(async move {
+            app_state
+                .game_timer(room, length, started)
+                .await
+                .inspect_err(|error| eprintln!("game timer error: {error:#?}"))
+        })
+        .abort_handle();
+
+        (Arc::new(task), started)
+    }
+}
+
+impl State {
+    pub fn game(&self) -> Option<&dyn Game> {
+        match self {
+            State::Game(game) => Some(game.as_ref()),
+            _ => None,
+        }
+    }
+
+    pub fn try_game<G: Game + 'static>(&mut self) -> Result<&mut G, GameError> {
+        match self {
+            State::Game(game) => game.as_mut().as_any_mut().downcast_mut(),
+            _ => None,
+        }
+        .ok_or(GameError::InvalidState { state: G::name() })
+    }
+}
+
+impl AppState {
+    pub fn update_game(
+        &self,
+        room: &str,
+        update: impl FnOnce(&mut dyn Game, Context) -> Result<Flow>,
+    ) -> Result<()> {
+        let mut lock = self.room_mut(room)?;
+
+        apply_update(self, room, lock.value_mut(), update)
+    }
+
+    async fn game_timer(&self, room: String, length: Duration, started: Instant) -> Result<()> {
+        tokio::time::sleep(length).await;
+
+        let grace = {
+            let lock = self.room(&room)?;
+            let Room { clients, state, .. } = lock.value();
+
+            let Some(game) = state.game() else {
+                return Ok(());
+            };
+
+            game.grace(Context {
+                app_state: self,
+                room: &room,
+                clients,
+            })
+        };
+
+        tokio::time::sleep(grace).await;
+
+        self.update_game(&room, |game, ctx| game.on_timer(ctx, started))
+    }
+}
+
+// runs `update` on the room's game and goes back to the lobby if it ended it
+pub fn apply_update(
+    app_state: &AppState,
+    room: &str,
+    current: &mut Room,
+    update: impl FnOnce(&mut dyn Game, Context) -> Result<Flow>,
+) -> Result<()> {
+    let Room { clients, state, .. } = &mut *current;
+
+    let State::Game(game) = state else {
+        return Err(GameError::InvalidState { state: "game" });
+    };
+
+    let ctx = Context {
+        app_state,
+        room,
+        clients,
+    };
+
+    if let Flow::End = update(game.as_mut(), ctx)? {
+        let (info, record) = game.post_game_info();
+        end_game(app_state, room, current, info, record);
+    }
+
+    Ok(())
+}
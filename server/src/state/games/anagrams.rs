@@ -1,32 +1,137 @@
 use crate::{
-    global::GLOBAL,
+    global::{Dictionary, GLOBAL},
     state::{
         error::{AnagramsError, GameError, Result},
-        lobby::end_game,
-        messages::{self, ServerMessage},
-        room::Room,
-        SenderInfo,
+        games::{Context, Flow, Game},
+        history::{GameRecord, PlayerStats},
+        messages::{self, ClientMessage, RoomStateInfo, ServerMessage},
+        replay::Replay,
+        room::RoomSettings,
     },
     utils::{filter_string, ClientUtils, Sorted},
-    AppState,
 };
-use serde::Serialize;
-use std::{collections::HashSet, sync::Arc, time::Duration};
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::task::AbortHandle;
 use uuid::Uuid;
 
+// every loaded dictionary has words of each of these lengths
+pub const WORD_LENGTHS: RangeInclusive<usize> = 5..=9;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AnagramsSettings {
+    pub rounds: u8,
+    pub word_length: usize,
+    // seconds
+    pub round_len: u64,
+    pub min_guess_len: usize,
+    pub difficulty: AnagramsDifficulty,
+}
+
+// easier words can be made into more sub words
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnagramsDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Default for AnagramsSettings {
+    fn default() -> Self {
+        Self {
+            rounds: 1,
+            word_length: 6,
+            round_len: 30,
+            min_guess_len: 2,
+            difficulty: AnagramsDifficulty::Medium,
+        }
+    }
+}
+
+impl AnagramsSettings {
+    pub fn validate(&self) -> Result<(), AnagramsError> {
+        let reason = if !(1..=10).contains(&self.rounds) {
+            "rounds must be between 1 and 10"
+        } else if !WORD_LENGTHS.contains(&self.word_length) {
+            "word length must be between 5 and 9"
+        } else if !(10..=300).contains(&self.round_len) {
+            "round length must be between 10 and 300 seconds"
+        } else if !(2..=self.word_length).contains(&self.min_guess_len) {
+            "minimum guess length must be between 2 and the word length"
+        } else {
+            return Ok(());
+        };
+
+        Err(AnagramsError::InvalidSettings { reason })
+    }
+
+    // ranks a handful of candidates by how many sub words they have and picks
+    // one from the third matching the difficulty
+    pub fn random_anagram(&self, dictionary: &Dictionary) -> (Arc<str>, String) {
+        const CANDIDATES: usize = 9;
+
+        let mut candidates: Vec<(usize, (Arc<str>, String))> = (0..CANDIDATES)
+            .map(|_| {
+                let (original, anagram) = dictionary
+                    .random_anagram(self.word_length)
+                    .expect("dictionaries are checked for every word length when loaded");
+                let sub_words = dictionary.sub_words(&original, self.min_guess_len).count();
+
+                (sub_words, (original, anagram))
+            })
+            .collect();
+
+        candidates.sort_by_key(|(sub_words, _)| Reverse(*sub_words));
+
+        let third = CANDIDATES / 3;
+        let start = match self.difficulty {
+            AnagramsDifficulty::Easy => 0,
+            AnagramsDifficulty::Medium => third,
+            AnagramsDifficulty::Hard => third * 2,
+        };
+
+        let index = thread_rng().gen_range(start..start + third);
+        candidates.swap_remove(index).1
+    }
+}
+
+const INTERMISSION_LEN: Duration = Duration::from_secs(8);
+
 #[derive(Debug)]
 pub struct Anagrams {
+    pub settings: AnagramsSettings,
+    pub dictionary: String,
+    pub started_at: Instant,
+    // word times are measured from here
+    pub round_started_at: Instant,
+    // also used for the intermission between rounds
     pub timer: Arc<AbortHandle>,
+    pub round: u8,
+    pub intermission: bool,
+    // possible words summed over finished rounds
+    pub total_possible: usize,
     pub anagram: String,
     pub original: String,
     pub players: Vec<Player>,
+    pub replay: Replay,
 }
 
 #[derive(Serialize, Debug, Clone)]
 pub struct Player {
     pub uuid: Uuid,
+    // only this round's words, earlier rounds are tallied into `points`
     pub used_words: HashSet<String>,
+    pub points: u32,
+    #[serde(skip_serializing)]
+    pub word_times: Vec<(Duration, String)>,
 }
 
 #[derive(Serialize)]
@@ -34,7 +139,7 @@ pub struct Player {
 pub enum GuessInfo {
     NotLongEnough,
     PromptMismatch,
-    NotEnglish,
+    NotInDictionary,
     AlreadyUsed,
     Valid,
 }
@@ -44,34 +149,147 @@ pub struct PostGameInfo {
     original_word: String,
     leaderboard: Vec<(Uuid, u32)>,
     used_words: Vec<(Uuid, HashSet<String>)>,
+    // every word in the last round, longest first
+    possible_words: PossibleWords,
+    completion: Vec<(Uuid, f32)>,
 }
 
+// (word, who found it)
+pub type PossibleWords = Vec<(String, Vec<Uuid>)>;
+
 impl Anagrams {
     pub fn check_guess(&mut self, uuid: Uuid, guess: &str) -> Result<GuessInfo, GameError> {
-        let guess_info = if guess.len() < 2 {
+        if self.intermission {
+            return Err(AnagramsError::BetweenRounds)?;
+        }
+
+        let guess_info = if guess.len() < self.settings.min_guess_len {
             GuessInfo::NotLongEnough
         } else if guess
             .chars()
             .any(|ch| guess.matches(ch).count() > self.anagram.matches(ch).count())
         {
             GuessInfo::PromptMismatch
-        } else if !GLOBAL.is_valid(guess) {
-            GuessInfo::NotEnglish
-        } else if self
-            .players
-            .iter_mut()
-            .find(|player| uuid == player.uuid)
-            .ok_or(AnagramsError::PlayerNotFound)?
-            .used_words
-            .insert(guess.to_string())
-        {
-            GuessInfo::Valid
+        } else if !GLOBAL.dictionary(&self.dictionary).is_valid(guess) {
+            GuessInfo::NotInDictionary
         } else {
-            GuessInfo::AlreadyUsed
+            let player = self
+                .players
+                .iter_mut()
+                .find(|player| uuid == player.uuid)
+                .ok_or(AnagramsError::PlayerNotFound)?;
+
+            if player.used_words.insert(guess.to_string()) {
+                player
+                    .word_times
+                    .push((self.round_started_at.elapsed(), guess.to_string()));
+
+                GuessInfo::Valid
+            } else {
+                GuessInfo::AlreadyUsed
+            }
         };
 
         Ok(guess_info)
     }
+
+    pub fn leaderboard(&self) -> Vec<(Uuid, u32)> {
+        leaderboard(&self.players)
+    }
+
+    // (every word that can be made this round with who found it, each player's share of them)
+    pub fn possible_words(&self) -> (PossibleWords, Vec<(Uuid, f32)>) {
+        tally_possible_words(
+            GLOBAL
+                .dictionary(&self.dictionary)
+                .sub_words(&self.original, self.settings.min_guess_len),
+            &self.players,
+        )
+    }
+
+    pub fn end_round(&mut self) -> ServerMessage {
+        let (possible_words, completion) = self.possible_words();
+        self.total_possible += possible_words.len();
+
+        let leaderboard = self.leaderboard();
+
+        for player in &mut self.players {
+            player.points += player
+                .used_words
+                .drain()
+                .map(|word| calculate_points(&word))
+                .sum::<u32>();
+        }
+
+        self.intermission = true;
+
+        ServerMessage::AnagramsRoundEnded {
+            round: self.round,
+            original_word: self.original.clone(),
+            possible_words,
+            completion,
+            leaderboard,
+        }
+    }
+
+    pub fn start_round(&mut self) -> ServerMessage {
+        let (original, anagram) = self
+            .settings
+            .random_anagram(&GLOBAL.dictionary(&self.dictionary));
+
+        self.original = original.to_string();
+        self.anagram = anagram;
+        self.round += 1;
+        self.round_started_at = Instant::now();
+        self.intermission = false;
+
+        ServerMessage::AnagramsRoundStarted {
+            round: self.round,
+            anagram: self.anagram.clone(),
+        }
+    }
+
+    pub fn record(&mut self, info: &PostGameInfo) -> GameRecord {
+        GameRecord {
+            started_at: self.started_at,
+            replay: std::mem::take(&mut self.replay),
+            placements: info.leaderboard.iter().map(|(uuid, _)| *uuid).collect(),
+            winners: 1,
+            words: self
+                .players
+                .iter()
+                .flat_map(|player| {
+                    player
+                        .word_times
+                        .iter()
+                        .map(|(time, word)| (player.uuid, *time, word.clone()))
+                })
+                .collect(),
+            stats: info
+                .leaderboard
+                .iter()
+                .filter_map(|(uuid, points)| {
+                    let player = self.players.iter().find(|player| player.uuid == *uuid)?;
+
+                    Some((
+                        *uuid,
+                        PlayerStats::Anagrams {
+                            points: *points,
+                            words_found: player.word_times.len() as u32,
+                            completion: player.word_times.len() as f32
+                                / self.total_possible.max(1) as f32,
+                            longest_word: player
+                                .word_times
+                                .iter()
+                                .map(|(_, word)| word)
+                                .max_by_key(|word| word.len())
+                                .cloned(),
+                        },
+                    ))
+                })
+                .collect(),
+        }
+    }
 }
 
 impl Player {
@@ -79,79 +297,169 @@ impl Player {
         Self {
             uuid,
             used_words: HashSet::new(),
+            points: 0,
+            word_times: Vec::new(),
         }
     }
 }
 
-impl AppState {
-    pub fn anagrams_guess(
-        &self,
-        SenderInfo { uuid, room }: SenderInfo,
-        mut guess: String,
-    ) -> Result<()> {
+impl Game for Anagrams {
+    fn name() -> &'static str {
+        "anagrams"
+    }
+
+    fn start(ctx: Context, players: &HashSet<Uuid>, settings: &RoomSettings) -> Self {
+        let dictionary = settings.dictionary.clone();
+        let settings = settings.anagrams.clone();
+        let (original, anagram) = settings.random_anagram(&GLOBAL.dictionary(&dictionary));
+        let (timer, started_at) = ctx.spawn_timer(Duration::from_secs(settings.round_len));
+
+        Anagrams {
+            settings,
+            dictionary,
+            started_at,
+            round_started_at: started_at,
+            timer,
+            round: 1,
+            intermission: false,
+            total_possible: 0,
+            anagram,
+            original: original.to_string(),
+            players: players.iter().map(|uuid| Player::new(*uuid)).collect(),
+            replay: Replay::new(started_at),
+        }
+    }
+
+    fn handle(&mut self, ctx: Context, uuid: Uuid, message: ClientMessage) -> Result<Flow> {
+        let ClientMessage::AnagramsGuess { word: mut guess } = message else {
+            return Err(GameError::UnexpectedMessage);
+        };
+
         filter_string(&mut guess);
 
-        if guess.len() > 6 {
+        if guess.len() > self.anagram.len() {
             return Err(AnagramsError::GuessTooLong)?;
         }
 
-        let mut lock = self.room_mut(room)?;
-        let Room { clients, state, .. } = lock.value_mut();
-        let game = state.try_anagrams()?;
-
-        match game.check_guess(uuid, &guess) {
-            Ok(GuessInfo::Valid) => {
-                clients.broadcast(ServerMessage::AnagramsCorrectGuess { uuid, guess });
+        match self.check_guess(uuid, &guess)? {
+            GuessInfo::Valid => {
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::AnagramsCorrectGuess { uuid, guess },
+                    &mut self.replay,
+                );
             }
-            Ok(reason) => {
-                clients[&uuid].send(ServerMessage::AnagramsInvalidGuess { reason });
+            reason => {
+                ctx.clients[&uuid].send(ServerMessage::AnagramsInvalidGuess { reason });
             }
-            Err(error) => return Err(error),
         }
 
-        Ok(())
+        Ok(Flow::Continue)
+    }
+
+    fn snapshot(&self, _uuid: Option<Uuid>) -> RoomStateInfo {
+        RoomStateInfo::Anagrams {
+            players: self.players.clone(),
+            anagram: self.anagram.clone(),
+            round: self.round,
+            intermission: self.intermission,
+        }
+    }
+
+    // the same timer runs each round and the intermissions between them
+    fn on_timer(&mut self, ctx: Context, _started: Instant) -> Result<Flow> {
+        let (message, length) = if self.intermission {
+            (
+                self.start_round(),
+                Duration::from_secs(self.settings.round_len),
+            )
+        } else if self.round < self.settings.rounds {
+            (self.end_round(), INTERMISSION_LEN)
+        } else {
+            return Ok(Flow::End);
+        };
+
+        ctx.clients.broadcast_recorded(message, &mut self.replay);
+        self.timer = ctx.spawn_timer(length).0;
+
+        Ok(Flow::Continue)
     }
 
-    pub async fn anagrams_timer(&self, room: String) -> Result<()> {
-        tokio::time::sleep(Duration::from_secs(30)).await;
+    fn post_game_info(&mut self) -> (messages::PostGameInfo, GameRecord) {
+        let info = get_post_game_info(self);
+        let record = self.record(&info);
 
-        let mut lock = self.room_mut(&room)?;
-        let Room {
-            clients,
-            state,
-            owner,
-            ..
-        } = lock.value_mut();
-        let game = state.try_anagrams()?;
+        (messages::PostGameInfo::Anagrams(info), record)
+    }
 
-        let game_info = messages::PostGameInfo::Anagrams(get_post_game_info(game));
-        end_game(state, clients, owner, game_info);
+    fn replay(&mut self) -> &mut Replay {
+        &mut self.replay
+    }
 
-        Ok(())
+    fn abort(&self) {
+        self.timer.abort();
     }
 }
 
-fn calculate_points(word: &str) -> u32 {
+pub fn calculate_points(word: &str) -> u32 {
     50 * 2_u32.pow(word.len() as u32 - 2)
 }
 
-fn get_post_game_info(game: &mut Anagrams) -> PostGameInfo {
-    PostGameInfo {
-        original_word: game.original.clone(),
-        leaderboard: game
-            .players
-            .iter()
-            .map(|player| {
-                (
-                    player.uuid,
-                    player
+pub fn leaderboard(players: &[Player]) -> Vec<(Uuid, u32)> {
+    players
+        .iter()
+        .map(|player| {
+            (
+                player.uuid,
+                player.points
+                    + player
                         .used_words
                         .iter()
                         .map(|word| calculate_points(word))
                         .sum::<u32>(),
-                )
-            })
-            .sorted_by_vec(|a, b| b.1.cmp(&a.1)),
+            )
+        })
+        .sorted_by_vec(|a, b| b.1.cmp(&a.1))
+}
+
+// pairs each possible word with who found it, and each player with their share of them
+pub fn tally_possible_words<'a>(
+    words: impl Iterator<Item = &'a str>,
+    players: &[Player],
+) -> (PossibleWords, Vec<(Uuid, f32)>) {
+    let possible_words: PossibleWords = words
+        .map(|word| {
+            let found_by = players
+                .iter()
+                .filter(|player| player.used_words.contains(word))
+                .map(|player| player.uuid)
+                .collect();
+
+            (word.to_string(), found_by)
+        })
+        .sorted_by_vec(|a, b| b.0.len().cmp(&a.0.len()));
+
+    let completion = players
+        .iter()
+        .map(|player| {
+            (
+                player.uuid,
+                player.used_words.len() as f32 / possible_words.len().max(1) as f32,
+            )
+        })
+        .sorted_by_vec(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+    (possible_words, completion)
+}
+
+fn get_post_game_info(game: &mut Anagrams) -> PostGameInfo {
+    let (possible_words, completion) = game.possible_words();
+    game.total_possible += possible_words.len();
+
+    PostGameInfo {
+        possible_words,
+        completion,
+        original_word: game.original.clone(),
+        leaderboard: game.leaderboard(),
         used_words: game
             .players
             .iter()
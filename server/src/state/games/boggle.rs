@@ -0,0 +1,488 @@
+use crate::{
+    global::{Dictionary, GLOBAL},
+    state::{
+        error::{BoggleError, GameError, Result},
+        games::{
+            anagrams::{self, PossibleWords},
+            Context, Flow, Game,
+        },
+        history::{GameRecord, PlayerStats},
+        messages::{self, ClientMessage, RoomStateInfo, ServerMessage},
+        replay::Replay,
+        room::RoomSettings,
+    },
+    utils::{filter_string, ClientUtils, UnixTime},
+};
+use rand::{seq::SliceRandom, thread_rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+// scrabble's tile distribution, minus the blanks
+const TILES: [(char, usize); 26] = [
+    ('a', 9),
+    ('b', 2),
+    ('c', 2),
+    ('d', 4),
+    ('e', 12),
+    ('f', 2),
+    ('g', 3),
+    ('h', 2),
+    ('i', 9),
+    ('j', 1),
+    ('k', 1),
+    ('l', 4),
+    ('m', 2),
+    ('n', 6),
+    ('o', 8),
+    ('p', 2),
+    ('q', 1),
+    ('r', 6),
+    ('s', 4),
+    ('t', 6),
+    ('u', 4),
+    ('v', 2),
+    ('w', 2),
+    ('x', 1),
+    ('y', 2),
+    ('z', 1),
+];
+
+// grids with fewer findable words than this get rerolled
+const MIN_POSSIBLE_WORDS: usize = 30;
+// practice grids are always this size, so tracing a word on one stays cheap
+const PRACTICE_GRID_SIZE: usize = 3;
+
+pub type Grid = Vec<Vec<char>>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct BoggleSettings {
+    pub size: usize,
+    // seconds
+    pub round_len: u64,
+    pub min_guess_len: usize,
+}
+
+impl Default for BoggleSettings {
+    fn default() -> Self {
+        Self {
+            size: 4,
+            round_len: 120,
+            min_guess_len: 3,
+        }
+    }
+}
+
+impl BoggleSettings {
+    pub fn validate(&self) -> Result<(), BoggleError> {
+        let reason = if !(4..=5).contains(&self.size) {
+            "grid size must be 4 or 5"
+        } else if !(30..=300).contains(&self.round_len) {
+            "round length must be between 30 and 300 seconds"
+        } else if !(3..=5).contains(&self.min_guess_len) {
+            "minimum guess length must be between 3 and 5"
+        } else {
+            return Ok(());
+        };
+
+        Err(BoggleError::InvalidSettings { reason })
+    }
+
+    // (grid, every word that can be found on it)
+    pub fn random_grid(&self) -> (Grid, Vec<Arc<str>>) {
+        let mut grid = random_grid(self.size);
+        let mut possible = possible_words(&grid, self.min_guess_len);
+
+        for _ in 0..10 {
+            if possible.len() >= MIN_POSSIBLE_WORDS {
+                break;
+            }
+
+            grid = random_grid(self.size);
+            possible = possible_words(&grid, self.min_guess_len);
+        }
+
+        (grid, possible)
+    }
+}
+
+#[derive(Debug)]
+pub struct Boggle {
+    pub settings: BoggleSettings,
+    pub started_at: Instant,
+    pub timer: Arc<AbortHandle>,
+    pub grid: Grid,
+    pub possible: Vec<Arc<str>>,
+    pub players: Vec<anagrams::Player>,
+    pub replay: Replay,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum GuessInfo {
+    NotLongEnough,
+    NotOnGrid,
+    NotInDictionary,
+    AlreadyUsed,
+    Valid,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PostGameInfo {
+    grid: Grid,
+    leaderboard: Vec<(Uuid, u32)>,
+    used_words: Vec<(Uuid, HashSet<String>)>,
+    // longest first
+    possible_words: PossibleWords,
+    completion: Vec<(Uuid, f32)>,
+}
+
+impl Boggle {
+    pub fn check_guess(&mut self, uuid: Uuid, guess: &str) -> Result<GuessInfo, GameError> {
+        let guess_info = if guess.len() < self.settings.min_guess_len {
+            GuessInfo::NotLongEnough
+        } else if !on_grid(&self.grid, guess) {
+            GuessInfo::NotOnGrid
+        } else if !GLOBAL.default_dictionary().is_valid(guess) {
+            GuessInfo::NotInDictionary
+        } else {
+            let player = self
+                .players
+                .iter_mut()
+                .find(|player| uuid == player.uuid)
+                .ok_or(BoggleError::PlayerNotFound)?;
+
+            if player.used_words.insert(guess.to_string()) {
+                player
+                    .word_times
+                    .push((self.started_at.elapsed(), guess.to_string()));
+
+                GuessInfo::Valid
+            } else {
+                GuessInfo::AlreadyUsed
+            }
+        };
+
+        Ok(guess_info)
+    }
+
+    pub fn deadline(&self) -> u64 {
+        let remaining =
+            Duration::from_secs(self.settings.round_len).saturating_sub(self.started_at.elapsed());
+
+        (SystemTime::now() + remaining).to_unix_millis()
+    }
+
+    pub fn record(&mut self, info: &PostGameInfo) -> GameRecord {
+        GameRecord {
+            started_at: self.started_at,
+            replay: std::mem::take(&mut self.replay),
+            placements: info.leaderboard.iter().map(|(uuid, _)| *uuid).collect(),
+            winners: 1,
+            words: self
+                .players
+                .iter()
+                .flat_map(|player| {
+                    player
+                        .word_times
+                        .iter()
+                        .map(|(time, word)| (player.uuid, *time, word.clone()))
+                })
+                .collect(),
+            stats: info
+                .leaderboard
+                .iter()
+                .filter_map(|(uuid, points)| {
+                    let player = self.players.iter().find(|player| player.uuid == *uuid)?;
+
+                    Some((
+                        *uuid,
+                        PlayerStats::Boggle {
+                            points: *points,
+                            completion: player.used_words.len() as f32
+                                / self.possible.len().max(1) as f32,
+                        },
+                    ))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Game for Boggle {
+    fn name() -> &'static str {
+        "boggle"
+    }
+
+    fn start(ctx: Context, players: &HashSet<Uuid>, settings: &RoomSettings) -> Self {
+        let settings = settings.boggle.clone();
+        let (grid, possible) = settings.random_grid();
+        let (timer, started_at) = ctx.spawn_timer(Duration::from_secs(settings.round_len));
+
+        Boggle {
+            settings,
+            started_at,
+            timer,
+            grid,
+            possible,
+            players: players
+                .iter()
+                .map(|uuid| anagrams::Player::new(*uuid))
+                .collect(),
+            replay: Replay::new(started_at),
+        }
+    }
+
+    fn handle(&mut self, ctx: Context, uuid: Uuid, message: ClientMessage) -> Result<Flow> {
+        let ClientMessage::BoggleGuess { word: mut guess } = message else {
+            return Err(GameError::UnexpectedMessage);
+        };
+
+        filter_string(&mut guess);
+
+        if guess.len() > self.settings.size * self.settings.size {
+            return Err(BoggleError::GuessTooLong)?;
+        }
+
+        match self.check_guess(uuid, &guess)? {
+            GuessInfo::Valid => {
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::BoggleCorrectGuess { uuid, guess },
+                    &mut self.replay,
+                );
+            }
+            reason => {
+                ctx.clients[&uuid].send(ServerMessage::BoggleInvalidGuess { reason });
+            }
+        }
+
+        Ok(Flow::Continue)
+    }
+
+    fn snapshot(&self, _uuid: Option<Uuid>) -> RoomStateInfo {
+        RoomStateInfo::Boggle {
+            players: self.players.clone(),
+            grid: self.grid.clone(),
+            deadline: self.deadline(),
+        }
+    }
+
+    fn on_timer(&mut self, _ctx: Context, started: Instant) -> Result<Flow> {
+        // a timer from an earlier game in the room that woke up late
+        if started != self.started_at {
+            return Ok(Flow::Continue);
+        }
+
+        Ok(Flow::End)
+    }
+
+    fn post_game_info(&mut self) -> (messages::PostGameInfo, GameRecord) {
+        let info = get_post_game_info(self);
+        let record = self.record(&info);
+
+        (messages::PostGameInfo::Boggle(info), record)
+    }
+
+    fn replay(&mut self) -> &mut Replay {
+        &mut self.replay
+    }
+
+    fn abort(&self) {
+        self.timer.abort();
+    }
+}
+
+// a small grid with its rows separated by spaces, for practicing in the lobby
+pub fn random_practice_grid() -> String {
+    random_grid(PRACTICE_GRID_SIZE)
+        .iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// reads back a grid from `random_practice_grid`, the client sends it so it could be anything
+pub fn parse_practice_grid(prompt: &str) -> Option<Grid> {
+    let grid: Grid = prompt
+        .split_whitespace()
+        .map(|row| row.chars().collect())
+        .collect();
+
+    let valid =
+        grid.len() == PRACTICE_GRID_SIZE && grid.iter().all(|row| row.len() == PRACTICE_GRID_SIZE);
+
+    valid.then_some(grid)
+}
+
+fn random_grid(size: usize) -> Grid {
+    let bag: Vec<char> = TILES
+        .iter()
+        .flat_map(|(tile, count)| (0..*count).map(|_| *tile))
+        .collect();
+    let tiles: Vec<char> = bag
+        .choose_multiple(&mut thread_rng(), size * size)
+        .copied()
+        .collect();
+
+    tiles.chunks(size).map(<[char]>::to_vec).collect()
+}
+
+fn neighbours(size: usize, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+    let rows = row.saturating_sub(1)..=(row + 1).min(size - 1);
+
+    rows.flat_map(move |r| (col.saturating_sub(1)..=(col + 1).min(size - 1)).map(move |c| (r, c)))
+        .filter(move |tile| *tile != (row, col))
+}
+
+// whether `word` can be traced through adjacent tiles without reusing any
+pub fn on_grid(grid: &Grid, word: &str) -> bool {
+    let word: Vec<char> = word.chars().collect();
+    let mut used = vec![vec![false; grid.len()]; grid.len()];
+
+    // words longer than the grid can't fit, and would only make the search slower
+    !word.is_empty()
+        && word.len() <= grid.len() * grid.len()
+        && (0..grid.len())
+            .any(|row| (0..grid.len()).any(|col| trace(grid, &word, row, col, &mut used)))
+}
+
+fn trace(grid: &Grid, word: &[char], row: usize, col: usize, used: &mut [Vec<bool>]) -> bool {
+    if used[row][col] || grid[row][col] != word[0] {
+        return false;
+    }
+
+    if word.len() == 1 {
+        return true;
+    }
+
+    used[row][col] = true;
+    let found = neighbours(grid.len(), row, col).any(|(r, c)| trace(grid, &word[1..], r, c, used));
+    used[row][col] = false;
+
+    found
+}
+
+// walks every path on the grid, giving up on one as soon as no word starts with it
+pub fn possible_words(grid: &Grid, min_len: usize) -> Vec<Arc<str>> {
+    let mut found = HashSet::new();
+    let mut used = vec![vec![false; grid.len()]; grid.len()];
+    let mut prefix = String::new();
+    let dictionary = GLOBAL.default_dictionary();
+
+    for row in 0..grid.len() {
+        for col in 0..grid.len() {
+            search(
+                &dictionary,
+                grid,
+                row,
+                col,
+                &mut prefix,
+                &mut used,
+                &mut found,
+            );
+        }
+    }
+
+    found
+        .into_iter()
+        .filter(|word| word.len() >= min_len)
+        .collect()
+}
+
+fn search(
+    dictionary: &Dictionary,
+    grid: &Grid,
+    row: usize,
+    col: usize,
+    prefix: &mut String,
+    used: &mut [Vec<bool>],
+    found: &mut HashSet<Arc<str>>,
+) {
+    if used[row][col] {
+        return;
+    }
+
+    prefix.push(grid[row][col]);
+    used[row][col] = true;
+
+    if let Ok(index) = dictionary
+        .words
+        .binary_search_by(|word| (**word).cmp(prefix))
+    {
+        found.insert(dictionary.words[index].clone());
+    }
+
+    if dictionary.continues(prefix) {
+        for (r, c) in neighbours(grid.len(), row, col) {
+            search(dictionary, grid, r, c, prefix, used, found);
+        }
+    }
+
+    prefix.pop();
+    used[row][col] = false;
+}
+
+fn get_post_game_info(game: &Boggle) -> PostGameInfo {
+    let (possible_words, completion) =
+        anagrams::tally_possible_words(game.possible.iter().map(|word| &**word), &game.players);
+
+    PostGameInfo {
+        grid: game.grid.clone(),
+        leaderboard: anagrams::leaderboard(&game.players),
+        used_words: game
+            .players
+            .iter()
+            .map(|player| (player.uuid, player.used_words.clone()))
+            .collect(),
+        possible_words,
+        completion,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &str) -> Grid {
+        parse_practice_grid(rows).unwrap()
+    }
+
+    #[test]
+    fn on_grid_follows_adjacent_tiles() {
+        let grid = grid("cat ore bed");
+
+        assert!(on_grid(&grid, "cat"));
+        // diagonals count as adjacent
+        assert!(on_grid(&grid, "crd"));
+        assert!(on_grid(&grid, "bore"));
+        assert!(!on_grid(&grid, "cad"));
+        assert!(!on_grid(&grid, "dog"));
+        assert!(!on_grid(&grid, ""));
+    }
+
+    #[test]
+    fn on_grid_uses_each_tile_once() {
+        let grid = grid("aab xyz xyz");
+
+        assert!(on_grid(&grid, "aab"));
+        assert!(!on_grid(&grid, "aaab"));
+        assert!(!on_grid(&grid, "bab"));
+        assert!(!on_grid(&grid, &"a".repeat(10)));
+    }
+
+    #[test]
+    fn practice_grids_must_match_the_practice_size() {
+        assert!(parse_practice_grid(&random_practice_grid()).is_some());
+        assert!(parse_practice_grid("abc def").is_none());
+        assert!(parse_practice_grid("abc de fgh").is_none());
+        assert!(parse_practice_grid("   ").is_none());
+        assert!(parse_practice_grid("ab cd").is_none());
+        assert!(parse_practice_grid(&vec!["a".repeat(20); 20].join(" ")).is_none());
+    }
+}
@@ -0,0 +1,470 @@
+use crate::{
+    global::GLOBAL,
+    state::{
+        error::{GameError, GreenteaError, Result},
+        games::{Context, Flow, Game},
+        history::{GameRecord, PlayerStats},
+        messages::{self, ClientMessage, RoomStateInfo, ServerMessage},
+        replay::Replay,
+        room::{Client, RoomSettings},
+    },
+    utils::{filter_string, ClientUtils, Sorted, UnixTime},
+};
+use rand::{seq::SliceRandom, thread_rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct GreenteaSettings {
+    pub rounds: u8,
+    // seconds
+    pub round_len: u64,
+    // letters are picked from word bomb prompts
+    pub min_wpm: usize,
+}
+
+impl Default for GreenteaSettings {
+    fn default() -> Self {
+        Self {
+            rounds: 8,
+            round_len: 15,
+            min_wpm: 500,
+        }
+    }
+}
+
+impl GreenteaSettings {
+    pub fn validate(&self) -> Result<(), GreenteaError> {
+        let reason = if !(1..=20).contains(&self.rounds) {
+            "rounds must be between 1 and 20"
+        } else if !(5..=60).contains(&self.round_len) {
+            "round length must be between 5 and 60 seconds"
+        } else if !(100..=1000).contains(&self.min_wpm) {
+            "minimum words per prompt must be between 100 and 1000"
+        } else {
+            return Ok(());
+        };
+
+        Err(GreenteaError::InvalidSettings { reason })
+    }
+
+    pub fn random_letters(&self) -> String {
+        let mut letters: Vec<char> = GLOBAL
+            .default_dictionary()
+            .prompts
+            .random_prompt(self.min_wpm)
+            .chars()
+            .collect();
+        letters.shuffle(&mut thread_rng());
+
+        letters.into_iter().collect()
+    }
+}
+
+const INTERMISSION_LEN: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub struct Greentea {
+    pub settings: GreenteaSettings,
+    pub started_at: Instant,
+    // runs out at the end of a round's guessing and again when the 5 second
+    // break after it is over, cut short once everyone has a word in
+    pub timer: Arc<AbortHandle>,
+    // when `timer` was spawned, to tell it apart from timers it replaced
+    pub timer_started: Instant,
+    pub round: u8,
+    pub round_started_at: Instant,
+    pub intermission: bool,
+    pub letters: String,
+    pub players: Vec<Player>,
+    pub replay: Replay,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Player {
+    pub uuid: Uuid,
+    pub points: u32,
+    // this round's word, hidden from everyone until the round ends
+    #[serde(skip_serializing)]
+    pub guess: Option<(String, u32)>,
+    #[serde(skip_serializing)]
+    pub word_times: Vec<(Duration, String)>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum GuessInfo {
+    PromptMismatch,
+    NotInDictionary,
+    Valid { points: u32 },
+}
+
+#[derive(Serialize, Clone)]
+pub struct PostGameInfo {
+    letters: String,
+    guesses: Vec<(Uuid, String, u32)>,
+    leaderboard: Vec<(Uuid, u32)>,
+    longest_words: Vec<(Uuid, String)>,
+}
+
+impl Greentea {
+    pub fn check_guess(&mut self, uuid: Uuid, guess: &str) -> Result<GuessInfo, GameError> {
+        if self.intermission {
+            return Err(GreenteaError::BetweenRounds)?;
+        }
+
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| uuid == player.uuid)
+            .ok_or(GreenteaError::PlayerNotFound)?;
+
+        if player.guess.is_some() {
+            return Err(GreenteaError::AlreadySubmitted)?;
+        }
+
+        let guess_info = if self
+            .letters
+            .chars()
+            .any(|ch| guess.matches(ch).count() < self.letters.matches(ch).count())
+        {
+            GuessInfo::PromptMismatch
+        } else if !GLOBAL.default_dictionary().is_valid(guess) {
+            GuessInfo::NotInDictionary
+        } else {
+            let round_len = Duration::from_secs(self.settings.round_len);
+            let points = calculate_points(guess, self.round_started_at.elapsed(), round_len);
+
+            player.guess = Some((guess.to_string(), points));
+            player
+                .word_times
+                .push((self.round_started_at.elapsed(), guess.to_string()));
+
+            GuessInfo::Valid { points }
+        };
+
+        Ok(guess_info)
+    }
+
+    // players who left don't hold up the round, they can still guess if they rejoin in time
+    pub fn everyone_submitted(&self, clients: &HashMap<Uuid, Client>) -> bool {
+        self.players
+            .iter()
+            .filter(|player| clients.is_connected(player.uuid))
+            .all(|player| player.guess.is_some())
+    }
+
+    pub fn deadline(&self) -> u64 {
+        let remaining = Duration::from_secs(self.settings.round_len)
+            .saturating_sub(self.round_started_at.elapsed());
+
+        (SystemTime::now() + remaining).to_unix_millis()
+    }
+
+    pub fn leaderboard(&self) -> Vec<(Uuid, u32)> {
+        self.players
+            .iter()
+            .map(|player| {
+                (
+                    player.uuid,
+                    player.points + player.guess.as_ref().map_or(0, |(_, points)| *points),
+                )
+            })
+            .sorted_by_vec(|a, b| b.1.cmp(&a.1))
+    }
+
+    // (uuid, word, points), highest scoring first
+    fn guesses(&self) -> Vec<(Uuid, String, u32)> {
+        self.players
+            .iter()
+            .filter_map(|player| {
+                let (word, points) = player.guess.clone()?;
+                Some((player.uuid, word, points))
+            })
+            .sorted_by_vec(|a, b| b.2.cmp(&a.2))
+    }
+
+    pub fn end_round(&mut self) -> ServerMessage {
+        let guesses = self.guesses();
+        let leaderboard = self.leaderboard();
+
+        for player in &mut self.players {
+            player.points += player.guess.take().map_or(0, |(_, points)| points);
+        }
+
+        self.intermission = true;
+
+        ServerMessage::GreenteaRoundEnded {
+            round: self.round,
+            letters: self.letters.clone(),
+            guesses,
+            leaderboard,
+        }
+    }
+
+    pub fn start_round(&mut self) -> ServerMessage {
+        self.letters = self.settings.random_letters();
+        self.round += 1;
+        self.round_started_at = Instant::now();
+        self.intermission = false;
+
+        ServerMessage::GreenteaRoundStarted {
+            round: self.round,
+            letters: self.letters.clone(),
+            deadline: self.deadline(),
+        }
+    }
+
+    pub fn record(&mut self, info: &PostGameInfo) -> GameRecord {
+        GameRecord {
+            started_at: self.started_at,
+            replay: std::mem::take(&mut self.replay),
+            placements: info.leaderboard.iter().map(|(uuid, _)| *uuid).collect(),
+            winners: 1,
+            words: self
+                .players
+                .iter()
+                .flat_map(|player| {
+                    player
+                        .word_times
+                        .iter()
+                        .map(|(time, word)| (player.uuid, *time, word.clone()))
+                })
+                .collect(),
+            stats: info
+                .leaderboard
+                .iter()
+                .map(|(uuid, points)| (*uuid, PlayerStats::Greentea { points: *points }))
+                .collect(),
+        }
+    }
+}
+
+impl Player {
+    pub fn new(uuid: Uuid) -> Self {
+        Self {
+            uuid,
+            points: 0,
+            guess: None,
+            word_times: Vec::new(),
+        }
+    }
+}
+
+impl Game for Greentea {
+    fn name() -> &'static str {
+        "greentea"
+    }
+
+    fn start(ctx: Context, players: &HashSet<Uuid>, settings: &RoomSettings) -> Self {
+        let settings = settings.greentea.clone();
+        let (timer, started_at) = ctx.spawn_timer(Duration::from_secs(settings.round_len));
+
+        Greentea {
+            letters: settings.random_letters(),
+            settings,
+            started_at,
+            timer,
+            timer_started: started_at,
+            round: 1,
+            round_started_at: started_at,
+            intermission: false,
+            players: players.iter().map(|uuid| Player::new(*uuid)).collect(),
+            replay: Replay::new(started_at),
+        }
+    }
+
+    fn handle(&mut self, ctx: Context, uuid: Uuid, message: ClientMessage) -> Result<Flow> {
+        let ClientMessage::GreenteaGuess { word: mut guess } = message else {
+            return Err(GameError::UnexpectedMessage);
+        };
+
+        filter_string(&mut guess);
+
+        if guess.len() > 35 {
+            return Err(GreenteaError::GuessTooLong)?;
+        }
+
+        match self.check_guess(uuid, &guess)? {
+            GuessInfo::Valid { points } => {
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::GreenteaCorrectGuess { uuid, points },
+                    &mut self.replay,
+                );
+
+                // no point waiting out the timer once everyone has a word in
+                if self.everyone_submitted(ctx.clients) {
+                    self.timer.abort();
+                    return Ok(self.finish_round(ctx));
+                }
+            }
+            reason => {
+                ctx.clients[&uuid].send(ServerMessage::GreenteaInvalidGuess { reason });
+            }
+        }
+
+        Ok(Flow::Continue)
+    }
+
+    fn snapshot(&self, _uuid: Option<Uuid>) -> RoomStateInfo {
+        RoomStateInfo::Greentea {
+            players: self.players.clone(),
+            letters: self.letters.clone(),
+            round: self.round,
+            intermission: self.intermission,
+            deadline: self.deadline(),
+            submitted: self
+                .players
+                .iter()
+                .filter(|player| player.guess.is_some())
+                .map(|player| player.uuid)
+                .collect(),
+        }
+    }
+
+    // whoever left may have been the last one without a word in
+    fn on_disconnect(&mut self, ctx: Context, _uuid: Uuid) -> Result<Flow> {
+        if self.intermission || !self.everyone_submitted(ctx.clients) {
+            return Ok(Flow::Continue);
+        }
+
+        self.timer.abort();
+        Ok(self.finish_round(ctx))
+    }
+
+    // guessing time running out ends the round, the break running out deals the next letters
+    fn on_timer(&mut self, ctx: Context, started: Instant) -> Result<Flow> {
+        // the round may have been ended early while this was waiting on the lock
+        if started != self.timer_started {
+            return Ok(Flow::Continue);
+        }
+
+        if !self.intermission {
+            return Ok(self.finish_round(ctx));
+        }
+
+        let round_started = self.start_round();
+        ctx.clients
+            .broadcast_recorded(round_started, &mut self.replay);
+        self.restart_timer(ctx, Duration::from_secs(self.settings.round_len));
+
+        Ok(Flow::Continue)
+    }
+
+    fn post_game_info(&mut self) -> (messages::PostGameInfo, GameRecord) {
+        let info = get_post_game_info(self);
+        let record = self.record(&info);
+
+        (messages::PostGameInfo::Greentea(info), record)
+    }
+
+    fn replay(&mut self) -> &mut Replay {
+        &mut self.replay
+    }
+
+    fn abort(&self) {
+        self.timer.abort();
+    }
+}
+
+impl Greentea {
+    fn finish_round(&mut self, ctx: Context) -> Flow {
+        if self.round >= self.settings.rounds {
+            return Flow::End;
+        }
+
+        let round_ended = self.end_round();
+        ctx.clients
+            .broadcast_recorded(round_ended, &mut self.replay);
+        self.restart_timer(ctx, INTERMISSION_LEN);
+
+        Flow::Continue
+    }
+
+    fn restart_timer(&mut self, ctx: Context, length: Duration) {
+        (self.timer, self.timer_started) = ctx.spawn_timer(length);
+    }
+}
+
+// up to 100 for speed plus 10 per letter
+fn calculate_points(word: &str, elapsed: Duration, round_len: Duration) -> u32 {
+    let time_left = round_len.saturating_sub(elapsed).as_secs_f32() / round_len.as_secs_f32();
+
+    (100.0 * time_left).round() as u32 + 10 * word.len() as u32
+}
+
+fn longest_word(player: &Player) -> Option<&String> {
+    player
+        .word_times
+        .iter()
+        .map(|(_, word)| word)
+        .max_by_key(|word| word.len())
+}
+
+fn get_post_game_info(game: &mut Greentea) -> PostGameInfo {
+    PostGameInfo {
+        letters: game.letters.clone(),
+        guesses: game.guesses(),
+        leaderboard: game.leaderboard(),
+        longest_words: game
+            .players
+            .iter()
+            .filter_map(|player| Some((player.uuid, longest_word(player)?.clone())))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faster_and_longer_words_score_more() {
+        let round_len = Duration::from_secs(20);
+
+        assert_eq!(calculate_points("tea", Duration::ZERO, round_len), 130);
+        assert_eq!(
+            calculate_points("tea", Duration::from_secs(5), round_len),
+            105
+        );
+        assert_eq!(
+            calculate_points("greentea", Duration::from_secs(5), round_len),
+            155
+        );
+    }
+
+    #[test]
+    fn late_words_only_score_for_letters() {
+        let round_len = Duration::from_secs(20);
+
+        assert_eq!(calculate_points("tea", round_len, round_len), 30);
+        // guesses that arrive after the deadline don't go negative
+        assert_eq!(
+            calculate_points("tea", Duration::from_secs(25), round_len),
+            30
+        );
+    }
+
+    #[test]
+    fn settings_are_checked() {
+        assert!(GreenteaSettings::default().validate().is_ok());
+
+        let settings = GreenteaSettings {
+            round_len: 90,
+            ..Default::default()
+        };
+        assert!(matches!(
+            settings.validate(),
+            Err(GreenteaError::InvalidSettings {
+                reason: "round length must be between 5 and 60 seconds"
+            })
+        ));
+    }
+}
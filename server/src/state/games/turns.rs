@@ -0,0 +1,221 @@
+use crate::{
+    state::games::Context,
+    utils::{Sorted, UnixTime},
+};
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+// guesses typed ahead (a prompt that came back after a miss) would otherwise
+// count as instant, which makes for infinite wpm
+pub const MIN_GUESS_TIME: Duration = Duration::from_millis(250);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct TurnSettings {
+    pub lives: u8,
+    // range the timer is picked from after it runs out
+    pub min_timer_len: f32,
+    pub max_timer_len: f32,
+    // the timer never has less than this left when a turn starts
+    pub min_turn_len: f32,
+}
+
+impl Default for TurnSettings {
+    fn default() -> Self {
+        Self {
+            lives: 2,
+            min_timer_len: 10.0,
+            max_timer_len: 30.0,
+            min_turn_len: 6.0,
+        }
+    }
+}
+
+impl TurnSettings {
+    // each game wraps the reason in its own settings error
+    pub fn invalid_reason(&self) -> Option<&'static str> {
+        if !(1..=10).contains(&self.lives) {
+            Some("starting lives must be between 1 and 10")
+        } else if !(1.0..=self.max_timer_len).contains(&self.min_timer_len)
+            || self.max_timer_len > 120.0
+        {
+            Some("timer range must be between 1 and 120 seconds")
+        } else if !(1.0..=self.max_timer_len).contains(&self.min_turn_len) {
+            Some("minimum turn length must be between 1 second and the max timer length")
+        } else {
+            None
+        }
+    }
+
+    pub fn random_timer_len(&self) -> f32 {
+        thread_rng().gen_range(self.min_timer_len..=self.max_timer_len)
+    }
+}
+
+#[derive(Debug)]
+pub struct Timer {
+    pub task: Arc<AbortHandle>,
+    pub start: Instant,
+    // seconds from `start`, carried over between turns until it runs out
+    pub length: f32,
+}
+
+impl Timer {
+    pub fn spawn(ctx: Context, length: f32) -> Self {
+        let (task, start) = ctx.spawn_timer(Duration::from_secs_f32(length));
+
+        Self {
+            task,
+            start,
+            length,
+        }
+    }
+
+    // each turn is timed from when its prompt is sent
+    pub fn restart(&mut self, ctx: Context) {
+        self.task.abort();
+        *self = Self::spawn(ctx, self.length);
+    }
+
+    // how long a guess took, less the time it's assumed to have been in flight
+    pub fn guess_time(&self, latency: Duration) -> Duration {
+        self.start
+            .elapsed()
+            .saturating_sub(latency)
+            .max(MIN_GUESS_TIME)
+    }
+
+    // unix ms when the timer runs out
+    pub fn deadline(&self) -> u64 {
+        let remaining = Duration::from_secs_f32(self.length).saturating_sub(self.start.elapsed());
+
+        (SystemTime::now() + remaining).to_unix_millis()
+    }
+}
+
+// give a guess that's still in flight from the player whose turn it is a chance to arrive
+pub fn turn_grace(ctx: Context, turn: Uuid) -> Duration {
+    ctx.clients
+        .get(&turn)
+        .map(|client| client.latency_allowance())
+        .unwrap_or_default()
+}
+
+// a player and the words they used with how long each took
+pub type UsedWords<'a> = (Uuid, &'a [(Duration, String)]);
+
+pub fn fastest_guesses(players: &[UsedWords]) -> Vec<(Uuid, f32)> {
+    players
+        .iter()
+        .filter_map(|(uuid, used_words)| {
+            used_words
+                .iter()
+                .map(|(duration, _)| duration.as_secs_f32())
+                .min_by(|a, b| a.partial_cmp(b).unwrap())
+                .map(|duration| (*uuid, duration))
+        })
+        .sorted_by_vec(|a, b| a.1.partial_cmp(&b.1).unwrap())
+}
+
+pub fn longest_words(players: &[UsedWords]) -> Vec<(Uuid, String)> {
+    players
+        .iter()
+        .filter_map(|(uuid, used_words)| {
+            used_words
+                .iter()
+                .max_by_key(|(_, word)| word.len())
+                .map(|(_, word)| (*uuid, word.clone()))
+        })
+        .sorted_by_vec(|a, b| b.1.len().cmp(&a.1.len()))
+}
+
+pub fn avg_word_lengths(players: &[UsedWords]) -> Vec<(Uuid, f32)> {
+    players
+        .iter()
+        .filter(|(_, used_words)| !used_words.is_empty())
+        .map(|(uuid, used_words)| {
+            (
+                *uuid,
+                used_words
+                    .iter()
+                    .map(|(_, word)| word.len() as f32)
+                    .sum::<f32>()
+                    / used_words.len() as f32,
+            )
+        })
+        .sorted_by_vec(|a, b| b.1.partial_cmp(&a.1).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_settings_are_valid() {
+        assert_eq!(TurnSettings::default().invalid_reason(), None);
+    }
+
+    #[test]
+    fn lives_are_checked() {
+        for lives in [0, 11] {
+            let settings = TurnSettings {
+                lives,
+                ..Default::default()
+            };
+            assert_eq!(
+                settings.invalid_reason(),
+                Some("starting lives must be between 1 and 10")
+            );
+        }
+    }
+
+    #[test]
+    fn timers_have_to_fit_inside_each_other() {
+        for (min_timer_len, max_timer_len) in [(20.0, 15.0), (0.5, 15.0), (10.0, 150.0)] {
+            let settings = TurnSettings {
+                min_timer_len,
+                max_timer_len,
+                ..Default::default()
+            };
+            assert_eq!(
+                settings.invalid_reason(),
+                Some("timer range must be between 1 and 120 seconds")
+            );
+        }
+
+        for min_turn_len in [0.5, 40.0] {
+            let settings = TurnSettings {
+                min_turn_len,
+                ..Default::default()
+            };
+            assert_eq!(
+                settings.invalid_reason(),
+                Some("minimum turn length must be between 1 second and the max timer length")
+            );
+        }
+    }
+
+    #[test]
+    fn stats_skip_players_without_words() {
+        let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let alice_words = [
+            (Duration::from_secs(3), "tea".to_string()),
+            (Duration::from_secs(1), "teapot".to_string()),
+        ];
+        let bob_words = [(Duration::from_secs(2), "kettles".to_string())];
+        let players: [UsedWords; 3] = [(alice, &alice_words), (bob, &bob_words), (carol, &[])];
+
+        assert_eq!(fastest_guesses(&players), [(alice, 1.0), (bob, 2.0)]);
+        assert_eq!(
+            longest_words(&players),
+            [(bob, "kettles".to_string()), (alice, "teapot".to_string())]
+        );
+        assert_eq!(avg_word_lengths(&players), [(bob, 7.0), (alice, 4.5)]);
+    }
+}
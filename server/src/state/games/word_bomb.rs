@@ -1,46 +1,124 @@
 use crate::{
     global::GLOBAL,
     state::{
+        bot::check_for_bot_turn,
         error::{GameError, Result, WordBombError},
-        lobby::end_game,
-        messages::{self, ServerMessage},
-        Room, SenderInfo,
+        games::{
+            turns::{self, turn_grace, Timer, TurnSettings, UsedWords},
+            Context, Flow, Game,
+        },
+        history::{GameRecord, PlayerStats},
+        messages::{self, ClientMessage, RoomStateInfo, ServerMessage},
+        replay::Replay,
+        room::RoomSettings,
     },
     utils::{filter_string, ClientUtils, Sorted},
-    AppState,
 };
-use rand::{thread_rng, Rng};
+use rand::{seq::SliceRandom, thread_rng};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashSet,
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::task::AbortHandle;
 use uuid::Uuid;
 
+const MAX_POWER_UPS: usize = 3;
+const RARE_LETTERS: [char; 4] = ['j', 'q', 'x', 'z'];
+// seconds
+const ADDED_TIME: f32 = 5.0;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
 pub struct WordBombSettings {
     pub min_wpm: usize,
+    #[serde(flatten)]
+    pub turns: TurnSettings,
+    pub max_lives: Option<u8>,
+    // how many times a prompt can be missed before it's replaced
+    pub prompt_uses: u8,
+    // letters that don't need to be used to earn an extra life
+    pub excluded_letters: HashSet<char>,
+    // how many teams players are split into, teammates share lives and letters
+    pub teams: Option<u8>,
+    // long words and rare letters earn power ups
+    pub power_ups: bool,
+    pub variant: Variant,
+}
+
+impl Default for WordBombSettings {
+    fn default() -> Self {
+        Self {
+            min_wpm: 500,
+            turns: TurnSettings::default(),
+            max_lives: None,
+            prompt_uses: 2,
+            excluded_letters: HashSet::from(['x', 'z']),
+            teams: None,
+            power_ups: false,
+            variant: Variant::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    #[default]
+    Classic,
+    // turn order flips whenever someone earns an extra life
+    Reverse,
+    // every word has to be longer than the one before it
+    Hardcore,
+    // words from the room's earlier games can't be used again
+    NoRepeats,
+}
+
+impl WordBombSettings {
+    pub fn validate(&self) -> Result<(), WordBombError> {
+        let reason = if let Some(reason) = self.turns.invalid_reason() {
+            reason
+        } else if self
+            .max_lives
+            .is_some_and(|max_lives| max_lives < self.turns.lives || max_lives > 10)
+        {
+            "max lives must be between starting lives and 10"
+        } else if !(1..=10).contains(&self.prompt_uses) {
+            "prompt uses must be between 1 and 10"
+        } else if self.excluded_letters.len() > 20
+            || !self.excluded_letters.iter().all(char::is_ascii_lowercase)
+        {
+            "excluded letters must be at most 20 lowercase letters"
+        } else if self.teams.is_some_and(|teams| !(2..=4).contains(&teams)) {
+            "teams must be between 2 and 4"
+        } else {
+            return Ok(());
+        };
+
+        Err(WordBombError::InvalidSettings { reason })
+    }
 }
 
 #[derive(Debug)]
 pub struct WordBomb {
     pub settings: WordBombSettings,
+    pub dictionary: String,
     pub started_at: Instant,
     pub timer: Timer,
-    pub prompt: &'static str,
+    pub prompt: Arc<str>,
     pub prompt_uses: u8,
-    pub missed_prompts: Vec<&'static str>,
+    pub missed_prompts: Vec<Arc<str>>,
+    pub eliminated: Vec<Uuid>,
     pub players: Vec<Player>,
+    // empty unless playing in teams
+    pub teams: Vec<Team>,
     pub turn: Uuid,
-}
-
-#[derive(Debug)]
-pub struct Timer {
-    pub task: Arc<AbortHandle>,
-    pub start: Instant,
-    pub length: f32,
+    pub reversed: bool,
+    // set until the next prompt is picked
+    pub harder_prompt: bool,
+    // reset whenever the bomb goes off
+    pub last_word_len: usize,
+    pub session_words: HashSet<String>,
+    pub replay: Replay,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -48,24 +126,70 @@ pub struct Player {
     pub uuid: Uuid,
     pub input: String,
     pub lives: u8,
+    // index into `WordBomb::teams`
+    pub team: Option<usize>,
+    pub power_ups: Vec<PowerUp>,
     #[serde(skip_serializing)]
     pub used_words: Vec<(Duration, String)>,
     #[serde(skip_serializing)]
     pub used_letters: HashSet<char>,
 }
 
+// a team's lives are mirrored onto each of its players
+#[derive(Debug, Default)]
+pub struct Team {
+    pub used_letters: HashSet<char>,
+    pub extra_lives: u8,
+    // turns rotate through a team's players
+    pub last_turn: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUp {
+    // passes the bomb on without losing a life
+    SkipTurn,
+    ReverseOrder,
+    AddTime,
+    // the next prompt fits fewer words than the room's minimum
+    HarderPrompt,
+}
+
+impl PowerUp {
+    fn earned(guess: &str) -> Option<Self> {
+        let earned = guess.len() >= 11 || guess.contains(RARE_LETTERS);
+
+        earned.then(|| {
+            *[
+                PowerUp::SkipTurn,
+                PowerUp::ReverseOrder,
+                PowerUp::AddTime,
+                PowerUp::HarderPrompt,
+            ]
+            .choose(&mut thread_rng())
+            .unwrap()
+        })
+    }
+}
+
 #[derive(Serialize)]
 #[serde(tag = "type")]
 pub enum GuessInfo {
     PromptNotIn,
-    NotEnglish,
+    NotInDictionary,
     AlreadyUsed,
-    Valid { extra_life: bool },
+    NotLongerThanLast,
+    UsedInEarlierGame,
+    Valid {
+        extra_life: bool,
+        power_up: Option<PowerUp>,
+    },
 }
 
 #[derive(Serialize, Debug, Clone)]
 pub struct PostGameInfo {
     winner: Uuid,
+    winning_team: Option<usize>,
+    teams: Vec<TeamStats>,
     mins_elapsed: f32,
     words_used: usize,
     fastest_guesses: Vec<(Uuid, f32)>,
@@ -74,18 +198,33 @@ pub struct PostGameInfo {
     avg_word_lengths: Vec<(Uuid, f32)>,
 }
 
+#[derive(Serialize, Debug, Clone)]
+pub struct TeamStats {
+    players: Vec<Uuid>,
+    words_used: usize,
+    extra_lives: u8,
+    avg_wpm: Option<f32>,
+}
+
 impl WordBomb {
-    pub fn check_guess(&mut self, guess: &str) -> Result<GuessInfo, GameError> {
-        let guess_info = if !guess.contains(self.prompt) {
+    // `latency` is how long the guess is assumed to have been in flight, it's
+    // taken off the guess time so slower connections aren't penalised
+    pub fn check_guess(&mut self, guess: &str, latency: Duration) -> Result<GuessInfo, GameError> {
+        let guess_info = if !guess.contains(&*self.prompt) {
             GuessInfo::PromptNotIn
-        } else if !GLOBAL.is_valid(guess) {
-            GuessInfo::NotEnglish
+        } else if !GLOBAL.dictionary(&self.dictionary).is_valid(guess) {
+            GuessInfo::NotInDictionary
         } else if self
             .players
             .iter()
             .any(|player| player.used_words.iter().any(|(_, word)| word == guess))
         {
             GuessInfo::AlreadyUsed
+        } else if self.settings.variant == Variant::Hardcore && guess.len() <= self.last_word_len {
+            GuessInfo::NotLongerThanLast
+        } else if self.settings.variant == Variant::NoRepeats && self.session_words.contains(guess)
+        {
+            GuessInfo::UsedInEarlierGame
         } else {
             let current_player = self
                 .players
@@ -93,54 +232,87 @@ impl WordBomb {
                 .find(|player| player.uuid == self.turn)
                 .ok_or(WordBombError::PlayerNotFound)?;
 
-            current_player.used_words.push((
-                Instant::now().duration_since(self.timer.start),
-                guess.to_string(),
-            ));
+            let elapsed = self.timer.guess_time(latency);
 
-            current_player
-                .used_letters
-                .extend(guess.chars().filter(|c| c.is_alphabetic()));
+            current_player.used_words.push((elapsed, guess.to_string()));
 
-            let extra_life = ('a'..='z')
-                .filter(|c| !['x', 'z'].contains(c))
-                .all(|c| current_player.used_letters.contains(&c));
+            let power_up = PowerUp::earned(guess).filter(|_| {
+                self.settings.power_ups && current_player.power_ups.len() < MAX_POWER_UPS
+            });
+
+            if let Some(power_up) = power_up {
+                current_player.power_ups.push(power_up);
+            }
+
+            let lives = current_player.lives;
+            let (used_letters, team_extra_lives) = match current_player
+                .team
+                .and_then(|team| self.teams.get_mut(team))
+            {
+                Some(team) => (&mut team.used_letters, Some(&mut team.extra_lives)),
+                None => (&mut current_player.used_letters, None),
+            };
+
+            used_letters.extend(guess.chars().filter(|c| c.is_alphabetic()));
+
+            // letters carry over while a player is at max lives
+            let extra_life = self.settings.max_lives != Some(lives)
+                && ('a'..='z')
+                    .filter(|c| !self.settings.excluded_letters.contains(c))
+                    .all(|c| used_letters.contains(&c));
 
             if extra_life {
-                current_player.lives += 1;
-                current_player.used_letters.clear();
+                used_letters.clear();
+
+                if self.settings.variant == Variant::Reverse {
+                    self.reversed = !self.reversed;
+                }
+
+                if let Some(extra_lives) = team_extra_lives {
+                    *extra_lives += 1;
+                }
+
+                self.set_lives(self.turn, lives + 1);
             }
 
-            self.timer.length = (self.timer.length
-                - Instant::now()
-                    .duration_since(self.timer.start)
-                    .as_secs_f32())
-            .max(6.0);
+            self.last_word_len = guess.len();
+            self.timer.length =
+                (self.timer.length - elapsed.as_secs_f32()).max(self.settings.turns.min_turn_len);
 
             self.new_prompt();
             self.update_turn()?;
 
-            GuessInfo::Valid { extra_life }
+            GuessInfo::Valid {
+                extra_life,
+                power_up,
+            }
         };
 
         Ok(guess_info)
     }
 
     pub fn player_timed_out(&mut self) -> Result<()> {
-        self.timer.length = thread_rng().gen_range(10.0..=30.0);
+        self.timer.length = self.settings.turns.random_timer_len();
 
-        self.missed_prompts.push(self.prompt);
+        self.missed_prompts.push(self.prompt.clone());
+        self.last_word_len = 0;
 
-        let player = self
+        let lives = self
             .players
-            .iter_mut()
+            .iter()
             .find(|player| player.uuid == self.turn)
-            .ok_or(WordBombError::PlayerNotFound)?;
+            .ok_or(WordBombError::PlayerNotFound)?
+            .lives
+            - 1;
 
-        player.lives -= 1;
+        self.set_lives(self.turn, lives);
+
+        if lives == 0 {
+            self.eliminated.extend(self.with_teammates(self.turn));
+        }
 
         self.prompt_uses += 1;
-        if self.prompt_uses > 1 {
+        if self.prompt_uses >= self.settings.prompt_uses {
             self.new_prompt();
         }
 
@@ -149,6 +321,99 @@ impl WordBomb {
         Ok(())
     }
 
+    pub fn use_power_up(&mut self, uuid: Uuid, power_up: PowerUp) -> Result<()> {
+        if !self.settings.power_ups {
+            return Err(WordBombError::PowerUpsDisabled)?;
+        }
+
+        if self.turn != uuid {
+            return Err(WordBombError::OutOfTurn)?;
+        }
+
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| player.uuid == uuid)
+            .ok_or(WordBombError::PlayerNotFound)?;
+
+        let index = player
+            .power_ups
+            .iter()
+            .position(|owned| *owned == power_up)
+            .ok_or(WordBombError::PowerUpNotOwned)?;
+
+        player.power_ups.remove(index);
+
+        let remaining = self.timer.length - self.timer.start.elapsed().as_secs_f32();
+
+        match power_up {
+            PowerUp::SkipTurn => {
+                self.timer.length = remaining.max(self.settings.turns.min_turn_len);
+                self.update_turn()?;
+            }
+            PowerUp::ReverseOrder => self.reversed = !self.reversed,
+            PowerUp::AddTime => self.timer.length = remaining + ADDED_TIME,
+            PowerUp::HarderPrompt => self.harder_prompt = true,
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, info: &PostGameInfo) -> GameRecord {
+        GameRecord {
+            started_at: self.started_at,
+            replay: std::mem::take(&mut self.replay),
+            placements: self
+                .alive_players()
+                .iter()
+                .map(|player| player.uuid)
+                .chain(self.eliminated.iter().rev().copied())
+                .collect(),
+            winners: self.alive_players().len().max(1),
+            words: self
+                .players
+                .iter()
+                .flat_map(|player| {
+                    player
+                        .used_words
+                        .iter()
+                        .map(|(time, word)| (player.uuid, *time, word.clone()))
+                })
+                .collect(),
+            stats: self
+                .players
+                .iter()
+                .map(|player| {
+                    let stat = |stats: &[(Uuid, f32)]| {
+                        stats
+                            .iter()
+                            .find(|(uuid, _)| *uuid == player.uuid)
+                            .map(|(_, stat)| *stat)
+                    };
+
+                    (
+                        player.uuid,
+                        PlayerStats::WordBomb {
+                            words_used: player.used_words.len() as u32,
+                            letters_used: player
+                                .used_words
+                                .iter()
+                                .map(|(_, word)| word.len() as u32)
+                                .sum(),
+                            avg_wpm: stat(&info.avg_wpms),
+                            fastest_guess: stat(&info.fastest_guesses),
+                            longest_word: info
+                                .longest_words
+                                .iter()
+                                .find(|(uuid, _)| *uuid == player.uuid)
+                                .map(|(_, word)| word.clone()),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
     pub fn alive_players(&self) -> Vec<&Player> {
         self.players
             .iter()
@@ -156,11 +421,68 @@ impl WordBomb {
             .collect()
     }
 
+    pub fn used_letters(&self, uuid: Uuid) -> Option<&HashSet<char>> {
+        let player = self.players.iter().find(|player| player.uuid == uuid)?;
+
+        match player.team {
+            Some(team) => self.teams.get(team).map(|team| &team.used_letters),
+            None => Some(&player.used_letters),
+        }
+    }
+
+    // the player along with their teammates, if they have any
+    fn with_teammates(&self, uuid: Uuid) -> Vec<Uuid> {
+        let team = self
+            .players
+            .iter()
+            .find(|player| player.uuid == uuid)
+            .and_then(|player| player.team);
+
+        self.players
+            .iter()
+            .filter(|player| player.uuid == uuid || (team.is_some() && player.team == team))
+            .map(|player| player.uuid)
+            .collect()
+    }
+
+    fn set_lives(&mut self, uuid: Uuid, lives: u8) {
+        let team = self.with_teammates(uuid);
+
+        for player in &mut self.players {
+            if team.contains(&player.uuid) {
+                player.lives = lives;
+            }
+        }
+    }
+
+    // players left standing, with teams counted as one
+    fn alive_sides(&self) -> usize {
+        let mut teams = HashSet::new();
+
+        self.alive_players()
+            .iter()
+            .filter(|player| match player.team {
+                Some(team) => teams.insert(team),
+                None => true,
+            })
+            .count()
+    }
+
     fn new_prompt(&mut self) {
         self.prompt_uses = 0;
 
+        let harder = std::mem::take(&mut self.harder_prompt);
+        let dictionary = GLOBAL.dictionary(&self.dictionary);
+        let min_wpm = self.settings.min_wpm;
+
         for _ in 0..10 {
-            let new_prompt = GLOBAL.prompts.random_prompt(self.settings.min_wpm);
+            let new_prompt = if harder {
+                dictionary
+                    .prompts
+                    .random_prompt_between(min_wpm / 5, min_wpm)
+            } else {
+                dictionary.prompts.random_prompt(min_wpm)
+            };
 
             if new_prompt != self.prompt {
                 self.prompt = new_prompt;
@@ -170,7 +492,10 @@ impl WordBomb {
     }
 
     fn update_turn(&mut self) -> Result<()> {
-        if self.alive_players().len() <= 1 {
+        // solo games last until the only player runs out of lives
+        let last_alive = if self.players.len() == 1 { 0 } else { 1 };
+
+        if self.alive_sides() <= last_alive {
             return Err(WordBombError::NoPlayersAlive)?;
         }
 
@@ -180,47 +505,213 @@ impl WordBomb {
             .position(|player| player.uuid == self.turn)
             .ok_or(WordBombError::PlayerNotFound)?;
 
-        let next_alive = self
+        let team = self.players[index].team;
+        // teams take turns, unless a solo player is their own team
+        let other_team = |player: &&Player| team.is_none() || player.team != team;
+
+        let len = self.players.len();
+        let (next_alive, next_team) = (1..=len)
+            .map(|offset| {
+                if self.reversed {
+                    &self.players[(index + len - offset) % len]
+                } else {
+                    &self.players[(index + offset) % len]
+                }
+            })
+            .filter(|player| player.lives > 0)
+            .find(other_team)
+            .or_else(|| self.players.iter().find(|player| player.lives > 0))
+            .map(|player| (player.uuid, player.team))
+            .ok_or(WordBombError::PlayerNotFound)?;
+
+        self.turn = match next_team {
+            Some(team) => self.next_teammate(team)?,
+            None => next_alive,
+        };
+
+        Ok(())
+    }
+
+    // whoever on `team` is up after the last of them to have a turn
+    fn next_teammate(&mut self, team: usize) -> Result<Uuid> {
+        let last_turn = self.teams[team].last_turn;
+        let start = last_turn
+            .and_then(|uuid| self.players.iter().position(|player| player.uuid == uuid))
+            .map_or(0, |index| index + 1);
+
+        let next = self
             .players
             .iter()
             .cycle()
-            .skip(index + 1)
-            .find(|player| player.lives > 0)
-            .ok_or(WordBombError::PlayerNotFound)?;
+            .skip(start)
+            .find(|player| player.team == Some(team))
+            .ok_or(WordBombError::PlayerNotFound)?
+            .uuid;
 
-        self.turn = next_alive.uuid;
+        self.teams[team].last_turn = Some(next);
 
-        Ok(())
+        Ok(next)
     }
 }
 
 impl Player {
-    pub fn new(uuid: Uuid) -> Self {
+    pub fn new(uuid: Uuid, lives: u8) -> Self {
         Self {
             uuid,
             input: String::new(),
-            lives: 2,
+            lives,
+            team: None,
+            power_ups: Vec::new(),
             used_letters: HashSet::new(),
             used_words: Vec::new(),
         }
     }
 }
 
-impl AppState {
-    pub fn word_bomb_input(
-        &self,
-        SenderInfo { uuid, room }: SenderInfo,
-        new_input: String,
-    ) -> Result<()> {
+// deals already shuffled players out into teams, the first player starts
+pub fn split_into_teams(players: &mut [Player], teams: u8) -> Vec<Team> {
+    let count = (teams as usize).min(players.len());
+
+    for (index, player) in players.iter_mut().enumerate() {
+        player.team = Some(index % count);
+    }
+
+    let mut teams: Vec<Team> = (0..count).map(|_| Team::default()).collect();
+
+    if let Some(first) = players.first() {
+        teams[0].last_turn = Some(first.uuid);
+    }
+
+    teams
+}
+
+impl Game for WordBomb {
+    fn name() -> &'static str {
+        "word bomb"
+    }
+
+    fn start(ctx: Context, players: &HashSet<Uuid>, settings: &RoomSettings) -> Self {
+        let dictionary = settings.dictionary.clone();
+        let settings = settings.word_bomb.clone();
+        let mut players: Vec<Player> = players
+            .iter()
+            .map(|uuid| Player::new(*uuid, settings.turns.lives))
+            .collect();
+        players.shuffle(&mut thread_rng());
+
+        let teams = settings
+            .teams
+            .map(|teams| split_into_teams(&mut players, teams))
+            .unwrap_or_default();
+
+        let timer = Timer::spawn(ctx, settings.turns.random_timer_len());
+
+        let game = WordBomb {
+            prompt: GLOBAL
+                .dictionary(&dictionary)
+                .prompts
+                .random_prompt(settings.min_wpm),
+            settings,
+            dictionary,
+            started_at: timer.start,
+            replay: Replay::new(timer.start),
+            timer,
+            prompt_uses: 0,
+            missed_prompts: Vec::new(),
+            eliminated: Vec::new(),
+            turn: players[0].uuid,
+            players,
+            teams,
+            reversed: false,
+            harder_prompt: false,
+            last_word_len: 0,
+            session_words: HashSet::new(),
+        };
+
+        check_for_bot_turn(ctx.app_state, ctx.room, ctx.clients, &game);
+
+        game
+    }
+
+    fn handle(&mut self, ctx: Context, uuid: Uuid, message: ClientMessage) -> Result<Flow> {
+        match message {
+            ClientMessage::WordBombInput { input } => self.client_input(ctx, uuid, input)?,
+            ClientMessage::WordBombGuess { word } => self.client_guess(ctx, uuid, word)?,
+            ClientMessage::WordBombPowerUp { power_up } => {
+                self.client_power_up(ctx, uuid, power_up)?
+            }
+            _ => return Err(GameError::UnexpectedMessage),
+        }
+
+        Ok(Flow::Continue)
+    }
+
+    fn snapshot(&self, uuid: Option<Uuid>) -> RoomStateInfo {
+        RoomStateInfo::WordBomb {
+            players: self.players.clone(),
+            turn: self.turn,
+            prompt: self.prompt.to_string(),
+            deadline: self.timer.deadline(),
+            used_letters: uuid.and_then(|uuid| self.used_letters(uuid)).cloned(),
+        }
+    }
+
+    fn grace(&self, ctx: Context) -> Duration {
+        turn_grace(ctx, self.turn)
+    }
+
+    fn on_timer(&mut self, ctx: Context, started: Instant) -> Result<Flow> {
+        if started != self.timer.start {
+            return Ok(Flow::Continue);
+        }
+
+        match self.player_timed_out() {
+            Ok(()) => {
+                self.timer.restart(ctx);
+
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::WordBombPrompt {
+                        correct_guess: None,
+                        life_change: -1,
+                        prompt: self.prompt.to_string(),
+                        turn: self.turn,
+                        deadline: self.timer.deadline(),
+                    },
+                    &mut self.replay,
+                );
+
+                check_for_bot_turn(ctx.app_state, ctx.room, ctx.clients, self);
+
+                Ok(Flow::Continue)
+            }
+            Err(GameError::WordBomb(WordBombError::NoPlayersAlive)) => Ok(Flow::End),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn post_game_info(&mut self) -> (messages::PostGameInfo, GameRecord) {
+        let info = get_post_game_info(self);
+        let record = self.record(&info);
+
+        (messages::PostGameInfo::WordBomb(info), record)
+    }
+
+    fn replay(&mut self) -> &mut Replay {
+        &mut self.replay
+    }
+
+    fn abort(&self) {
+        self.timer.task.abort();
+    }
+}
+
+impl WordBomb {
+    fn client_input(&mut self, ctx: Context, uuid: Uuid, new_input: String) -> Result<()> {
         if new_input.len() > 35 {
             return Err(WordBombError::InputTooLong)?;
         }
 
-        let mut lock = self.room_mut(room)?;
-        let Room { clients, state, .. } = lock.value_mut();
-        let game = state.try_word_bomb()?;
-
-        let player = game
+        let player = self
             .players
             .iter_mut()
             .find(|player| player.uuid == uuid)
@@ -228,175 +719,231 @@ impl AppState {
 
         player.input.clone_from(&new_input);
 
-        clients.broadcast(ServerMessage::WordBombInput {
-            uuid,
-            input: new_input,
-        });
+        ctx.clients.broadcast_recorded(
+            ServerMessage::WordBombInput {
+                uuid,
+                input: new_input,
+            },
+            &mut self.replay,
+        );
 
         Ok(())
     }
 
-    pub fn word_bomb_guess(
-        &self,
-        SenderInfo { uuid, room }: SenderInfo,
-        mut guess: String,
-    ) -> Result<()> {
+    fn client_guess(&mut self, ctx: Context, uuid: Uuid, mut guess: String) -> Result<()> {
         filter_string(&mut guess);
 
         if guess.len() > 35 {
             return Err(WordBombError::GuessTooLong)?;
         }
 
-        let mut lock = self.room_mut(room)?;
-        let Room { clients, state, .. } = lock.value_mut();
-        let game = state.try_word_bomb()?;
-
-        if game.turn != uuid {
+        if self.turn != uuid {
             return Err(WordBombError::OutOfTurn)?;
         }
 
-        match game.check_guess(&guess) {
-            Ok(GuessInfo::Valid { extra_life }) => {
-                clients.broadcast(ServerMessage::WordBombPrompt {
-                    correct_guess: Some(guess),
-                    life_change: extra_life.into(),
-                    prompt: game.prompt.to_string(),
-                    turn: game.turn,
-                });
-
-                game.timer.task.abort();
-                spawn_timeout_task(self.clone(), game, room.to_string());
+        let latency = ctx.clients[&uuid].latency_allowance();
+
+        match self.check_guess(&guess, latency)? {
+            GuessInfo::Valid {
+                extra_life,
+                power_up,
+            } => {
+                self.timer.restart(ctx);
+
+                if let Some(power_up) = power_up {
+                    ctx.clients.broadcast_recorded(
+                        ServerMessage::WordBombPowerUpEarned { uuid, power_up },
+                        &mut self.replay,
+                    );
+                }
+
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::WordBombPrompt {
+                        correct_guess: Some(guess),
+                        life_change: extra_life.into(),
+                        prompt: self.prompt.to_string(),
+                        turn: self.turn,
+                        deadline: self.timer.deadline(),
+                    },
+                    &mut self.replay,
+                );
+
+                check_for_bot_turn(ctx.app_state, ctx.room, ctx.clients, self);
             }
-            Ok(reason) => {
-                clients.broadcast(ServerMessage::WordBombInvalidGuess { uuid, reason });
+            reason => {
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::WordBombInvalidGuess { uuid, reason },
+                    &mut self.replay,
+                );
             }
-            Err(error) => return Err(error),
         };
 
         Ok(())
     }
 
-    pub async fn word_bomb_timer(
-        &self,
-        room: String,
-        timer_len: f32,
-        original_prompt: &str,
-    ) -> Result<()> {
-        tokio::time::sleep(Duration::from_secs_f32(timer_len)).await;
-
-        let mut lock = self.room_mut(&room)?;
-        let Room {
-            clients,
-            state,
-            owner,
-            ..
-        } = lock.value_mut();
-        let game = state.try_word_bomb()?;
-
-        if original_prompt == game.prompt {
-            match game.player_timed_out() {
-                Ok(()) => {
-                    clients.broadcast(ServerMessage::WordBombPrompt {
-                        correct_guess: None,
-                        life_change: -1,
-                        prompt: game.prompt.to_string(),
-                        turn: game.turn,
-                    });
+    fn client_power_up(&mut self, ctx: Context, uuid: Uuid, power_up: PowerUp) -> Result<()> {
+        self.use_power_up(uuid, power_up)?;
 
-                    spawn_timeout_task(self.clone(), game, room);
-                }
-                Err(GameError::WordBomb(WordBombError::NoPlayersAlive)) => {
-                    let game_info = messages::PostGameInfo::WordBomb(get_post_game_info(game));
-                    end_game(state, clients, owner, game_info);
-                }
-                Err(error) => Err(error)?,
-            }
+        if matches!(power_up, PowerUp::SkipTurn | PowerUp::AddTime) {
+            self.timer.restart(ctx);
         }
 
-        Ok(())
-    }
-}
+        ctx.clients.broadcast_recorded(
+            ServerMessage::WordBombPowerUpUsed {
+                uuid,
+                power_up,
+                turn: self.turn,
+                deadline: self.timer.deadline(),
+            },
+            &mut self.replay,
+        );
 
-fn spawn_timeout_task(app_state: AppState, game: &mut WordBomb, room: String) {
-    let timer_len = game.timer.length;
-    let current_prompt = game.prompt;
+        check_for_bot_turn(ctx.app_state, ctx.room, ctx.clients, self);
 
-    game.timer.task = Arc::new(
-        tokio::spawn(async move {
-            app_state
-                .word_bomb_timer(room, timer_len, current_prompt)
-                .await
-                .inspect_err(|error| eprintln!("word bomb timer error: {error:#?}"))
-        })
-        .abort_handle(),
-    );
+        Ok(())
+    }
 }
 
 fn get_post_game_info(game: &mut WordBomb) -> PostGameInfo {
+    let winner = game
+        .alive_players()
+        .first()
+        .map(|player| player.uuid)
+        .or(game.eliminated.last().copied())
+        .unwrap();
+    let used_words: Vec<UsedWords> = game
+        .players
+        .iter()
+        .map(|player| (player.uuid, player.used_words.as_slice()))
+        .collect();
+
     PostGameInfo {
-        winner: game.alive_players().first().unwrap().uuid,
-        mins_elapsed: Instant::now().duration_since(game.started_at).as_secs_f32() / 60.0,
-        words_used: game
+        winner,
+        winning_team: game
             .players
             .iter()
-            .map(|player| player.used_words.len())
-            .sum(),
-        fastest_guesses: game
-            .players
+            .find(|player| player.uuid == winner)
+            .and_then(|player| player.team),
+        teams: game
+            .teams
             .iter()
-            .filter_map(|player| {
-                player
-                    .used_words
+            .enumerate()
+            .map(|(index, team)| {
+                let players: Vec<&Player> = game
+                    .players
                     .iter()
-                    .map(|(duration, _)| duration.as_secs_f32())
-                    .min_by(|a, b| a.partial_cmp(b).unwrap())
-                    .map(|duration| (player.uuid, duration))
-            })
-            .sorted_by_vec(|a, b| a.1.partial_cmp(&b.1).unwrap()),
-        longest_words: game
-            .players
-            .iter()
-            .filter_map(|player| {
-                player
-                    .used_words
+                    .filter(|player| player.team == Some(index))
+                    .collect();
+                let words: Vec<&(Duration, String)> = players
                     .iter()
-                    .max_by_key(|(_, word)| word.len())
-                    .map(|(_, word)| (player.uuid, word.clone()))
+                    .flat_map(|player| &player.used_words)
+                    .collect();
+
+                TeamStats {
+                    players: players.iter().map(|player| player.uuid).collect(),
+                    words_used: words.len(),
+                    extra_lives: team.extra_lives,
+                    avg_wpm: (!words.is_empty()).then(|| {
+                        words
+                            .iter()
+                            .map(|(duration, word)| wpm(duration, word))
+                            .sum::<f32>()
+                            / words.len() as f32
+                    }),
+                }
             })
-            .sorted_by_vec(|a, b| b.1.len().cmp(&a.1.len())),
-        avg_wpms: game
+            .collect(),
+        mins_elapsed: Instant::now().duration_since(game.started_at).as_secs_f32() / 60.0,
+        words_used: game
             .players
             .iter()
-            .filter(|player| !player.used_letters.is_empty())
-            .map(|player| {
-                (
-                    player.uuid,
-                    player
-                        .used_words
-                        .iter()
-                        .map(|(duration, word)| {
-                            (word.len() as f32 / 5.0) / (duration.as_secs_f32() / 60.0)
-                        })
-                        .sum::<f32>()
-                        / player.used_words.len() as f32,
-                )
-            })
-            .sorted_by_vec(|a, b| b.1.partial_cmp(&a.1).unwrap()),
-        avg_word_lengths: game
+            .map(|player| player.used_words.len())
+            .sum(),
+        fastest_guesses: turns::fastest_guesses(&used_words),
+        longest_words: turns::longest_words(&used_words),
+        avg_wpms: game
             .players
             .iter()
-            .filter(|player| !player.used_letters.is_empty())
+            .filter(|player| !player.used_words.is_empty())
             .map(|player| {
                 (
                     player.uuid,
                     player
                         .used_words
                         .iter()
-                        .map(|(_, word)| word.len() as f32)
+                        .map(|(duration, word)| wpm(duration, word))
                         .sum::<f32>()
                         / player.used_words.len() as f32,
                 )
             })
             .sorted_by_vec(|a, b| b.1.partial_cmp(&a.1).unwrap()),
+        avg_word_lengths: turns::avg_word_lengths(&used_words),
+    }
+}
+
+fn wpm(duration: &Duration, word: &str) -> f32 {
+    (word.len() as f32 / 5.0) / (duration.as_secs_f32() / 60.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_reason(settings: WordBombSettings) -> Option<&'static str> {
+        match settings.validate() {
+            Ok(()) => None,
+            Err(WordBombError::InvalidSettings { reason }) => Some(reason),
+            Err(error) => panic!("unexpected error {error:?}"),
+        }
+    }
+
+    #[test]
+    fn default_settings_are_valid() {
+        assert_eq!(invalid_reason(WordBombSettings::default()), None);
+    }
+
+    #[test]
+    fn max_lives_cant_be_below_starting_lives() {
+        let turns = TurnSettings {
+            lives: 3,
+            ..Default::default()
+        };
+        let settings = WordBombSettings {
+            turns: turns.clone(),
+            max_lives: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            invalid_reason(settings),
+            Some("max lives must be between starting lives and 10")
+        );
+
+        let settings = WordBombSettings {
+            turns,
+            max_lives: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(invalid_reason(settings), None);
+    }
+
+    #[test]
+    fn excluded_letters_and_teams_are_checked() {
+        let settings = WordBombSettings {
+            excluded_letters: HashSet::from(['X']),
+            ..Default::default()
+        };
+        assert_eq!(
+            invalid_reason(settings),
+            Some("excluded letters must be at most 20 lowercase letters")
+        );
+
+        let settings = WordBombSettings {
+            teams: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(
+            invalid_reason(settings),
+            Some("teams must be between 2 and 4")
+        );
     }
 }
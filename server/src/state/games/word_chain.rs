@@ -0,0 +1,471 @@
+use crate::{
+    global::GLOBAL,
+    state::{
+        error::{GameError, Result, WordChainError},
+        games::{
+            turns::{self, turn_grace, Timer, TurnSettings, UsedWords},
+            Context, Flow, Game,
+        },
+        history::{GameRecord, PlayerStats},
+        messages::{self, ClientMessage, RoomStateInfo, ServerMessage},
+        replay::Replay,
+        room::RoomSettings,
+    },
+    utils::{filter_string, ClientUtils},
+};
+use rand::{seq::SliceRandom, thread_rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WordChainSettings {
+    #[serde(flatten)]
+    pub turns: TurnSettings,
+    // how many letters from the end of a word the next one has to start with
+    pub chain_len: usize,
+}
+
+impl Default for WordChainSettings {
+    fn default() -> Self {
+        Self {
+            turns: TurnSettings::default(),
+            chain_len: 1,
+        }
+    }
+}
+
+impl WordChainSettings {
+    pub fn validate(&self) -> Result<(), WordChainError> {
+        let reason = if let Some(reason) = self.turns.invalid_reason() {
+            reason
+        } else if !(1..=3).contains(&self.chain_len) {
+            "chain length must be between 1 and 3 letters"
+        } else {
+            return Ok(());
+        };
+
+        Err(WordChainError::InvalidSettings { reason })
+    }
+
+    // the start of a random word, used to begin a chain and after a miss
+    pub fn random_prompt(&self) -> String {
+        let dictionary = GLOBAL.default_dictionary();
+
+        loop {
+            let word = dictionary.words.choose(&mut thread_rng()).unwrap();
+
+            // `get` skips words where the cut would split a character
+            if word.len() > self.chain_len {
+                if let Some(start) = word.get(..self.chain_len) {
+                    break start.to_string();
+                }
+            }
+        }
+    }
+
+    // the end of `word`, cut short if nothing carries on from it
+    fn next_prompt(&self, word: &str) -> String {
+        (1..=self.chain_len.min(word.len()))
+            .rev()
+            .filter_map(|len| word.get(word.len() - len..))
+            .find(|suffix| GLOBAL.default_dictionary().continues(suffix))
+            .map_or_else(|| self.random_prompt(), str::to_string)
+    }
+}
+
+#[derive(Debug)]
+pub struct WordChain {
+    pub settings: WordChainSettings,
+    pub started_at: Instant,
+    pub timer: Timer,
+    // letters the next word has to start with
+    pub prompt: String,
+    pub eliminated: Vec<Uuid>,
+    pub players: Vec<Player>,
+    pub turn: Uuid,
+    pub replay: Replay,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Player {
+    pub uuid: Uuid,
+    pub input: String,
+    pub lives: u8,
+    #[serde(skip_serializing)]
+    pub used_words: Vec<(Duration, String)>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum GuessInfo {
+    PromptMismatch,
+    NotLongEnough,
+    NotInDictionary,
+    AlreadyUsed,
+    Valid,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PostGameInfo {
+    winner: Uuid,
+    mins_elapsed: f32,
+    words_used: usize,
+    fastest_guesses: Vec<(Uuid, f32)>,
+    longest_words: Vec<(Uuid, String)>,
+    avg_word_lengths: Vec<(Uuid, f32)>,
+}
+
+impl WordChain {
+    // `latency` is credited the same way as in word bomb
+    pub fn check_guess(&mut self, guess: &str, latency: Duration) -> Result<GuessInfo, GameError> {
+        let guess_info = if !guess.starts_with(&self.prompt) {
+            GuessInfo::PromptMismatch
+        } else if guess.len() <= self.prompt.len() {
+            GuessInfo::NotLongEnough
+        } else if !GLOBAL.default_dictionary().is_valid(guess) {
+            GuessInfo::NotInDictionary
+        } else if self
+            .players
+            .iter()
+            .any(|player| player.used_words.iter().any(|(_, word)| word == guess))
+        {
+            GuessInfo::AlreadyUsed
+        } else {
+            let current_player = self
+                .players
+                .iter_mut()
+                .find(|player| player.uuid == self.turn)
+                .ok_or(WordChainError::PlayerNotFound)?;
+
+            let elapsed = self.timer.guess_time(latency);
+
+            current_player.used_words.push((elapsed, guess.to_string()));
+
+            self.timer.length =
+                (self.timer.length - elapsed.as_secs_f32()).max(self.settings.turns.min_turn_len);
+
+            self.prompt = self.settings.next_prompt(guess);
+            self.update_turn()?;
+
+            GuessInfo::Valid
+        };
+
+        Ok(guess_info)
+    }
+
+    pub fn player_timed_out(&mut self) -> Result<()> {
+        self.timer.length = self.settings.turns.random_timer_len();
+
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| player.uuid == self.turn)
+            .ok_or(WordChainError::PlayerNotFound)?;
+
+        player.lives -= 1;
+
+        if player.lives == 0 {
+            self.eliminated.push(player.uuid);
+        }
+
+        // the chain is broken, so start a new one
+        self.prompt = self.settings.random_prompt();
+        self.update_turn()?;
+
+        Ok(())
+    }
+
+    pub fn record(&mut self) -> GameRecord {
+        GameRecord {
+            started_at: self.started_at,
+            replay: std::mem::take(&mut self.replay),
+            placements: self
+                .alive_players()
+                .iter()
+                .map(|player| player.uuid)
+                .chain(self.eliminated.iter().rev().copied())
+                .collect(),
+            winners: 1,
+            words: self
+                .players
+                .iter()
+                .flat_map(|player| {
+                    player
+                        .used_words
+                        .iter()
+                        .map(|(time, word)| (player.uuid, *time, word.clone()))
+                })
+                .collect(),
+            stats: self
+                .players
+                .iter()
+                .map(|player| {
+                    (
+                        player.uuid,
+                        PlayerStats::WordChain {
+                            words_used: player.used_words.len() as u32,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn alive_players(&self) -> Vec<&Player> {
+        self.players
+            .iter()
+            .filter(|player| player.lives > 0)
+            .collect()
+    }
+
+    fn update_turn(&mut self) -> Result<()> {
+        // a solo chain carries on until its player runs out of lives
+        let last_alive = if self.players.len() == 1 { 0 } else { 1 };
+
+        if self.alive_players().len() <= last_alive {
+            return Err(WordChainError::NoPlayersAlive)?;
+        }
+
+        let index = self
+            .players
+            .iter()
+            .position(|player| player.uuid == self.turn)
+            .ok_or(WordChainError::PlayerNotFound)?;
+
+        let next_alive = self
+            .players
+            .iter()
+            .cycle()
+            .skip(index + 1)
+            .find(|player| player.lives > 0)
+            .ok_or(WordChainError::PlayerNotFound)?;
+
+        self.turn = next_alive.uuid;
+
+        Ok(())
+    }
+}
+
+impl Player {
+    pub fn new(uuid: Uuid, lives: u8) -> Self {
+        Self {
+            uuid,
+            input: String::new(),
+            lives,
+            used_words: Vec::new(),
+        }
+    }
+}
+
+impl Game for WordChain {
+    fn name() -> &'static str {
+        "word chain"
+    }
+
+    fn start(ctx: Context, players: &HashSet<Uuid>, settings: &RoomSettings) -> Self {
+        let settings = settings.word_chain.clone();
+        let mut players: Vec<Player> = players
+            .iter()
+            .map(|uuid| Player::new(*uuid, settings.turns.lives))
+            .collect();
+        players.shuffle(&mut thread_rng());
+
+        let timer = Timer::spawn(ctx, settings.turns.random_timer_len());
+
+        WordChain {
+            prompt: settings.random_prompt(),
+            settings,
+            started_at: timer.start,
+            replay: Replay::new(timer.start),
+            timer,
+            eliminated: Vec::new(),
+            turn: players[0].uuid,
+            players,
+        }
+    }
+
+    fn handle(&mut self, ctx: Context, uuid: Uuid, message: ClientMessage) -> Result<Flow> {
+        match message {
+            ClientMessage::WordChainInput { input } => self.client_input(ctx, uuid, input)?,
+            ClientMessage::WordChainGuess { word } => self.client_guess(ctx, uuid, word)?,
+            _ => return Err(GameError::UnexpectedMessage),
+        }
+
+        Ok(Flow::Continue)
+    }
+
+    fn snapshot(&self, _uuid: Option<Uuid>) -> RoomStateInfo {
+        RoomStateInfo::WordChain {
+            players: self.players.clone(),
+            turn: self.turn,
+            prompt: self.prompt.clone(),
+            deadline: self.timer.deadline(),
+        }
+    }
+
+    fn grace(&self, ctx: Context) -> Duration {
+        turn_grace(ctx, self.turn)
+    }
+
+    fn on_timer(&mut self, ctx: Context, started: Instant) -> Result<Flow> {
+        if started != self.timer.start {
+            return Ok(Flow::Continue);
+        }
+
+        match self.player_timed_out() {
+            Ok(()) => {
+                self.timer.restart(ctx);
+
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::WordChainPrompt {
+                        correct_guess: None,
+                        life_change: -1,
+                        prompt: self.prompt.clone(),
+                        turn: self.turn,
+                        deadline: self.timer.deadline(),
+                    },
+                    &mut self.replay,
+                );
+
+                Ok(Flow::Continue)
+            }
+            Err(GameError::WordChain(WordChainError::NoPlayersAlive)) => Ok(Flow::End),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn post_game_info(&mut self) -> (messages::PostGameInfo, GameRecord) {
+        let info = get_post_game_info(self);
+        let record = self.record();
+
+        (messages::PostGameInfo::WordChain(info), record)
+    }
+
+    fn replay(&mut self) -> &mut Replay {
+        &mut self.replay
+    }
+
+    fn abort(&self) {
+        self.timer.task.abort();
+    }
+}
+
+impl WordChain {
+    fn client_input(&mut self, ctx: Context, uuid: Uuid, new_input: String) -> Result<()> {
+        if new_input.len() > 35 {
+            return Err(WordChainError::InputTooLong)?;
+        }
+
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| player.uuid == uuid)
+            .ok_or(WordChainError::PlayerNotFound)?;
+
+        player.input.clone_from(&new_input);
+
+        ctx.clients.broadcast_recorded(
+            ServerMessage::WordChainInput {
+                uuid,
+                input: new_input,
+            },
+            &mut self.replay,
+        );
+
+        Ok(())
+    }
+
+    fn client_guess(&mut self, ctx: Context, uuid: Uuid, mut guess: String) -> Result<()> {
+        filter_string(&mut guess);
+
+        if guess.len() > 35 {
+            return Err(WordChainError::GuessTooLong)?;
+        }
+
+        if self.turn != uuid {
+            return Err(WordChainError::OutOfTurn)?;
+        }
+
+        let latency = ctx.clients[&uuid].latency_allowance();
+
+        match self.check_guess(&guess, latency)? {
+            GuessInfo::Valid => {
+                self.timer.restart(ctx);
+
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::WordChainPrompt {
+                        correct_guess: Some(guess),
+                        life_change: 0,
+                        prompt: self.prompt.clone(),
+                        turn: self.turn,
+                        deadline: self.timer.deadline(),
+                    },
+                    &mut self.replay,
+                );
+            }
+            reason => {
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::WordChainInvalidGuess { uuid, reason },
+                    &mut self.replay,
+                );
+            }
+        };
+
+        Ok(())
+    }
+}
+
+fn get_post_game_info(game: &WordChain) -> PostGameInfo {
+    let used_words: Vec<UsedWords> = game
+        .players
+        .iter()
+        .map(|player| (player.uuid, player.used_words.as_slice()))
+        .collect();
+
+    PostGameInfo {
+        winner: game
+            .alive_players()
+            .first()
+            .map(|player| player.uuid)
+            .or(game.eliminated.last().copied())
+            .unwrap(),
+        mins_elapsed: game.started_at.elapsed().as_secs_f32() / 60.0,
+        words_used: game
+            .players
+            .iter()
+            .map(|player| player.used_words.len())
+            .sum(),
+        fastest_guesses: turns::fastest_guesses(&used_words),
+        longest_words: turns::longest_words(&used_words),
+        avg_word_lengths: turns::avg_word_lengths(&used_words),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the lives and timer checks are shared with word bomb and tested in `turns`
+    #[test]
+    fn chain_length_is_checked() {
+        assert!(WordChainSettings::default().validate().is_ok());
+
+        for chain_len in [0, 4] {
+            let settings = WordChainSettings {
+                chain_len,
+                ..Default::default()
+            };
+            assert!(matches!(
+                settings.validate(),
+                Err(WordChainError::InvalidSettings {
+                    reason: "chain length must be between 1 and 3 letters"
+                })
+            ));
+        }
+    }
+}
@@ -0,0 +1,496 @@
+use crate::{
+    global::GLOBAL,
+    state::{
+        error::{GameError, Result, WordleError},
+        games::{Context, Flow, Game},
+        history::{GameRecord, PlayerStats},
+        messages::{self, ClientMessage, RoomStateInfo, ServerMessage},
+        replay::Replay,
+        room::{Client, RoomSettings},
+    },
+    utils::{filter_string, ClientUtils, Sorted, UnixTime},
+};
+use rand::{seq::index, thread_rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+pub const WORD_LEN: usize = 5;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WordleSettings {
+    pub max_guesses: u8,
+    // seconds
+    pub time_limit: u64,
+    // everyone gets the same word for the whole day instead of a random one
+    pub daily: bool,
+}
+
+impl Default for WordleSettings {
+    fn default() -> Self {
+        Self {
+            max_guesses: 6,
+            time_limit: 180,
+            daily: true,
+        }
+    }
+}
+
+impl WordleSettings {
+    pub fn validate(&self) -> Result<(), WordleError> {
+        let reason = if !(3..=10).contains(&self.max_guesses) {
+            "max guesses must be between 3 and 10"
+        } else if !(30..=600).contains(&self.time_limit) {
+            "time limit must be between 30 and 600 seconds"
+        } else {
+            return Ok(());
+        };
+
+        Err(WordleError::InvalidSettings { reason })
+    }
+
+    pub fn pick_word(&self) -> Arc<str> {
+        let dictionary = GLOBAL.default_dictionary();
+
+        if self.daily {
+            dictionary.daily_word(WORD_LEN)
+        } else {
+            dictionary.random_word(WORD_LEN)
+        }
+        .expect("dictionaries are checked for every word length when loaded")
+    }
+}
+
+#[derive(Debug)]
+pub struct Wordle {
+    pub settings: WordleSettings,
+    pub started_at: Instant,
+    pub timer: Arc<AbortHandle>,
+    pub word: Arc<str>,
+    pub players: Vec<Player>,
+    pub replay: Replay,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Player {
+    pub uuid: Uuid,
+    pub guesses: u8,
+    // most letters in the right spot in any one guess
+    pub correct: u8,
+    pub solved: bool,
+    // feedback is only ever sent to the player who guessed
+    #[serde(skip_serializing)]
+    pub board: Vec<Guess>,
+    #[serde(skip_serializing)]
+    pub solved_in: Option<Duration>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Guess {
+    pub word: String,
+    pub feedback: Vec<Feedback>,
+    #[serde(skip_serializing)]
+    pub time: Duration,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feedback {
+    Correct,
+    Present,
+    Absent,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum GuessInfo {
+    WrongLength,
+    NotInDictionary,
+    Valid,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PostGameInfo {
+    word: String,
+    standings: Vec<Standing>,
+    boards: Vec<(Uuid, Vec<Guess>)>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct Standing {
+    uuid: Uuid,
+    solved: bool,
+    guesses: u8,
+    secs: Option<f32>,
+}
+
+impl Wordle {
+    pub fn check_guess(&mut self, uuid: Uuid, guess: &str) -> Result<GuessInfo, GameError> {
+        let max_guesses = self.settings.max_guesses;
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| uuid == player.uuid)
+            .ok_or(WordleError::PlayerNotFound)?;
+
+        if player.finished(max_guesses) {
+            return Err(WordleError::AlreadyFinished)?;
+        }
+
+        let guess_info = if guess.len() != WORD_LEN {
+            GuessInfo::WrongLength
+        } else if !GLOBAL.default_dictionary().is_valid(guess) {
+            GuessInfo::NotInDictionary
+        } else {
+            let feedback = feedback(&self.word, guess);
+            let correct = feedback
+                .iter()
+                .filter(|letter| **letter == Feedback::Correct)
+                .count() as u8;
+
+            player.guesses += 1;
+            player.correct = player.correct.max(correct);
+            player.board.push(Guess {
+                word: guess.to_string(),
+                feedback,
+                time: self.started_at.elapsed(),
+            });
+
+            if guess == &*self.word {
+                player.solved = true;
+                player.solved_in = Some(self.started_at.elapsed());
+            }
+
+            GuessInfo::Valid
+        };
+
+        Ok(guess_info)
+    }
+
+    // players who left don't keep the game going, their board is still there if they rejoin
+    pub fn everyone_finished(&self, clients: &HashMap<Uuid, Client>) -> bool {
+        self.players
+            .iter()
+            .filter(|player| clients.is_connected(player.uuid))
+            .all(|player| player.finished(self.settings.max_guesses))
+    }
+
+    pub fn deadline(&self) -> u64 {
+        let remaining =
+            Duration::from_secs(self.settings.time_limit).saturating_sub(self.started_at.elapsed());
+
+        (SystemTime::now() + remaining).to_unix_millis()
+    }
+
+    pub fn board(&self, uuid: Uuid) -> Vec<Guess> {
+        self.players
+            .iter()
+            .find(|player| player.uuid == uuid)
+            .map(|player| player.board.clone())
+            .unwrap_or_default()
+    }
+
+    // solvers by fewest guesses then fastest, everyone else by how close they got
+    fn standings(&self) -> Vec<&Player> {
+        self.players.iter().sorted_by_vec(|a, b| {
+            b.solved
+                .cmp(&a.solved)
+                .then_with(|| match (a.solved_in, b.solved_in) {
+                    (Some(a_time), Some(b_time)) => {
+                        a.guesses.cmp(&b.guesses).then(a_time.cmp(&b_time))
+                    }
+                    _ => b.correct.cmp(&a.correct).then(a.guesses.cmp(&b.guesses)),
+                })
+        })
+    }
+
+    pub fn record(&mut self) -> GameRecord {
+        let max_guesses = self.settings.max_guesses;
+
+        GameRecord {
+            started_at: self.started_at,
+            replay: std::mem::take(&mut self.replay),
+            placements: self.standings().iter().map(|player| player.uuid).collect(),
+            winners: 1,
+            words: self
+                .players
+                .iter()
+                .flat_map(|player| {
+                    player
+                        .board
+                        .iter()
+                        .map(|guess| (player.uuid, guess.time, guess.word.clone()))
+                })
+                .collect(),
+            stats: self
+                .players
+                .iter()
+                .map(|player| {
+                    (
+                        player.uuid,
+                        PlayerStats::Wordle {
+                            points: player.points(max_guesses),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Player {
+    pub fn new(uuid: Uuid) -> Self {
+        Self {
+            uuid,
+            guesses: 0,
+            correct: 0,
+            solved: false,
+            board: Vec::new(),
+            solved_in: None,
+        }
+    }
+
+    fn finished(&self, max_guesses: u8) -> bool {
+        self.solved || self.guesses >= max_guesses
+    }
+
+    // one point per guess left over, solving on the last guess is still worth one
+    fn points(&self, max_guesses: u8) -> u32 {
+        if self.solved {
+            (max_guesses + 1 - self.guesses) as u32
+        } else {
+            0
+        }
+    }
+}
+
+impl Game for Wordle {
+    fn name() -> &'static str {
+        "wordle"
+    }
+
+    fn start(ctx: Context, players: &HashSet<Uuid>, settings: &RoomSettings) -> Self {
+        let settings = settings.wordle.clone();
+        let (timer, started_at) = ctx.spawn_timer(Duration::from_secs(settings.time_limit));
+
+        Wordle {
+            word: settings.pick_word(),
+            settings,
+            started_at,
+            timer,
+            players: players.iter().map(|uuid| Player::new(*uuid)).collect(),
+            replay: Replay::new(started_at),
+        }
+    }
+
+    fn handle(&mut self, ctx: Context, uuid: Uuid, message: ClientMessage) -> Result<Flow> {
+        let ClientMessage::WordleGuess { word: mut guess } = message else {
+            return Err(GameError::UnexpectedMessage);
+        };
+
+        filter_string(&mut guess);
+
+        if guess.len() > 35 {
+            return Err(WordleError::GuessTooLong)?;
+        }
+
+        match self.check_guess(uuid, &guess)? {
+            GuessInfo::Valid => {
+                let player = self
+                    .players
+                    .iter()
+                    .find(|player| player.uuid == uuid)
+                    .ok_or(WordleError::PlayerNotFound)?;
+
+                ctx.clients[&uuid].send(ServerMessage::WordleFeedback {
+                    guess: player.board.last().cloned().unwrap(),
+                });
+                ctx.clients.broadcast_recorded(
+                    ServerMessage::WordleProgress {
+                        uuid,
+                        guesses: player.guesses,
+                        correct: player.correct,
+                        solved: player.solved,
+                    },
+                    &mut self.replay,
+                );
+
+                if self.everyone_finished(ctx.clients) {
+                    self.timer.abort();
+                    return Ok(Flow::End);
+                }
+            }
+            reason => {
+                ctx.clients[&uuid].send(ServerMessage::WordleInvalidGuess { reason });
+            }
+        }
+
+        Ok(Flow::Continue)
+    }
+
+    // boards are only shown to the player they belong to
+    fn snapshot(&self, uuid: Option<Uuid>) -> RoomStateInfo {
+        RoomStateInfo::Wordle {
+            players: self.players.clone(),
+            deadline: self.deadline(),
+            board: uuid.map(|uuid| self.board(uuid)).unwrap_or_default(),
+        }
+    }
+
+    // whoever left may have been the last one still guessing
+    fn on_disconnect(&mut self, ctx: Context, _uuid: Uuid) -> Result<Flow> {
+        if !self.everyone_finished(ctx.clients) {
+            return Ok(Flow::Continue);
+        }
+
+        self.timer.abort();
+        Ok(Flow::End)
+    }
+
+    fn on_timer(&mut self, _ctx: Context, started: Instant) -> Result<Flow> {
+        // everyone may have finished while this was waiting on the lock, and
+        // a new game started since
+        if started != self.started_at {
+            return Ok(Flow::Continue);
+        }
+
+        Ok(Flow::End)
+    }
+
+    fn post_game_info(&mut self) -> (messages::PostGameInfo, GameRecord) {
+        let info = get_post_game_info(self);
+        let record = self.record();
+
+        (messages::PostGameInfo::Wordle(info), record)
+    }
+
+    fn replay(&mut self) -> &mut Replay {
+        &mut self.replay
+    }
+
+    fn abort(&self) {
+        self.timer.abort();
+    }
+}
+
+// letters in the right spot are marked first so repeated letters aren't over counted
+fn feedback(word: &str, guess: &str) -> Vec<Feedback> {
+    let word = word.as_bytes();
+    let guess = guess.as_bytes();
+    let mut feedback = vec![Feedback::Absent; guess.len()];
+    let mut unmatched = [0u8; 26];
+
+    for (i, (&expected, &actual)) in word.iter().zip(guess).enumerate() {
+        if expected == actual {
+            feedback[i] = Feedback::Correct;
+        } else {
+            if let Some(count) = unmatched.get_mut(expected.wrapping_sub(b'a') as usize) {
+                *count += 1;
+            }
+        }
+    }
+
+    // anything outside a to z can only ever be correct or absent
+    for (i, &actual) in guess.iter().enumerate() {
+        let Some(count) = unmatched.get_mut(actual.wrapping_sub(b'a') as usize) else {
+            continue;
+        };
+
+        if feedback[i] != Feedback::Correct && *count > 0 {
+            feedback[i] = Feedback::Present;
+            *count -= 1;
+        }
+    }
+
+    feedback
+}
+
+// a word with a couple of letters blanked out, for practicing in the lobby
+pub fn random_pattern() -> String {
+    let word = GLOBAL
+        .default_dictionary()
+        .random_word(WORD_LEN)
+        .expect("dictionaries are checked for every word length when loaded");
+    let hidden = index::sample(&mut thread_rng(), WORD_LEN, 2);
+
+    word.chars()
+        .enumerate()
+        .map(|(i, ch)| {
+            if hidden.iter().any(|j| i == j) {
+                '_'
+            } else {
+                ch
+            }
+        })
+        .collect()
+}
+
+fn get_post_game_info(game: &Wordle) -> PostGameInfo {
+    PostGameInfo {
+        word: game.word.to_string(),
+        standings: game
+            .standings()
+            .iter()
+            .map(|player| Standing {
+                uuid: player.uuid,
+                solved: player.solved,
+                guesses: player.guesses,
+                secs: player.solved_in.map(|time| time.as_secs_f32()),
+            })
+            .collect(),
+        boards: game
+            .players
+            .iter()
+            .map(|player| (player.uuid, player.board.clone()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Feedback::*;
+
+    #[test]
+    fn feedback_marks_exact_matches() {
+        assert_eq!(feedback("crane", "crane"), vec![Correct; 5]);
+        assert_eq!(feedback("crane", "moist"), vec![Absent; 5]);
+        assert_eq!(
+            feedback("crane", "nacre"),
+            vec![Present, Present, Present, Present, Correct]
+        );
+    }
+
+    #[test]
+    fn feedback_counts_repeated_letters_once() {
+        // only one e is left over once the correct one is taken
+        assert_eq!(
+            feedback("eerie", "emcee"),
+            vec![Correct, Absent, Absent, Present, Correct]
+        );
+        // a repeated guess letter is only present as often as the word has it
+        assert_eq!(
+            feedback("apple", "paper"),
+            vec![Present, Present, Correct, Present, Absent]
+        );
+        assert_eq!(
+            feedback("abbey", "babes"),
+            vec![Present, Present, Correct, Correct, Absent]
+        );
+    }
+
+    #[test]
+    fn feedback_handles_letters_outside_a_to_z() {
+        assert_eq!(
+            feedback("cr\u{e9}me", "cr\u{e9}me"),
+            vec![Correct; "cr\u{e9}me".len()]
+        );
+        assert_eq!(feedback("crane", "CRANE"), vec![Absent; 5]);
+        assert_eq!(feedback("cr\u{e9}me", "crane")[..2], [Correct, Correct]);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{
+    db,
+    state::{
+        rating,
+        replay::Replay,
+        room::{Client, RoomSettings},
+    },
+    utils::UnixTime,
+    AppState,
+};
+use sqlx::SqlitePool;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime},
+};
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub struct GameRecord {
+    pub started_at: Instant,
+    // winner first
+    pub placements: Vec<Uuid>,
+    // how many of the placements share the win, teammates win together
+    pub winners: usize,
+    pub words: Vec<(Uuid, Duration, String)>,
+    pub stats: Vec<(Uuid, PlayerStats)>,
+    pub replay: Replay,
+}
+
+#[derive(Debug)]
+pub enum PlayerStats {
+    WordBomb {
+        words_used: u32,
+        letters_used: u32,
+        avg_wpm: Option<f32>,
+        fastest_guess: Option<f32>,
+        longest_word: Option<String>,
+    },
+    Anagrams {
+        points: u32,
+        words_found: u32,
+        // share of every possible word found
+        completion: f32,
+        longest_word: Option<String>,
+    },
+    Greentea {
+        points: u32,
+    },
+    WordChain {
+        words_used: u32,
+    },
+    Boggle {
+        points: u32,
+        // share of every word on the grid found
+        completion: f32,
+    },
+    Wordle {
+        points: u32,
+    },
+}
+
+impl AppState {
+    pub fn save_game(
+        &self,
+        room: &str,
+        settings: &RoomSettings,
+        clients: &HashMap<Uuid, Client>,
+        record: GameRecord,
+    ) {
+        let ended_at = SystemTime::now();
+        let duration = record.started_at.elapsed();
+        let winners = &record.placements[..record.winners.min(record.placements.len())];
+        let solo = record.placements.len() == 1;
+        let bots = record
+            .placements
+            .iter()
+            .any(|uuid| clients.get(uuid).is_some_and(|client| client.bot.is_some()));
+
+        let personal_best = if solo {
+            record.stats.first().and_then(|(uuid, stats)| {
+                let discord_id = clients.get(uuid)?.user.as_ref()?.discord_id.clone();
+
+                Some(stats.personal_best(discord_id, settings.game.as_str(), duration))
+            })
+        } else {
+            None
+        };
+
+        // solo games only count towards personal bests, practising alone
+        // shouldn't cost anyone their win rate, and wins over bots could be farmed
+        let stats: Vec<db::StatsUpdate> = if solo || bots {
+            Vec::new()
+        } else {
+            record
+                .stats
+                .into_iter()
+                .filter_map(|(uuid, stats)| {
+                    let discord_id = clients.get(&uuid)?.user.as_ref()?.discord_id.clone();
+                    let won = winners.contains(&uuid);
+
+                    stats.into_update(discord_id, won)
+                })
+                .collect()
+        };
+
+        // ranked rooms only allow signed in players, so everyone has an account
+        let ranked: Vec<String> = if settings.ranked {
+            record
+                .placements
+                .iter()
+                .filter_map(|uuid| Some(clients.get(uuid)?.user.as_ref()?.discord_id.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let game = db::NewGame {
+            room: room.to_string(),
+            game: settings.game.as_str(),
+            settings: serde_json::to_string(settings).unwrap(),
+            started_at: (ended_at - duration).to_unix_timestamp(),
+            duration_ms: duration.as_millis() as u32,
+            players: record
+                .placements
+                .iter()
+                .zip(1..)
+                .filter_map(|(uuid, placement)| {
+                    clients.get(uuid).map(|client| db::NewGamePlayer {
+                        uuid: uuid.to_string(),
+                        username: client.username.clone(),
+                        discord_id: client.user.as_ref().map(|user| user.discord_id.clone()),
+                        placement,
+                    })
+                })
+                .collect(),
+            words: record
+                .words
+                .into_iter()
+                .map(|(uuid, time, word)| db::NewGameWord {
+                    uuid: uuid.to_string(),
+                    word,
+                    time_ms: time.as_millis() as u32,
+                })
+                .collect(),
+        };
+
+        let replay = record
+            .replay
+            .compress()
+            .inspect_err(|error| eprintln!("failed to compress replay: {error}"))
+            .ok();
+
+        let db = self.db.clone();
+
+        tokio::spawn(async move {
+            persist_game(
+                &db,
+                game,
+                replay,
+                ended_at.to_unix_timestamp(),
+                stats,
+                ranked,
+                personal_best,
+            )
+            .await
+            .inspect_err(|error| eprintln!("failed to save game: {error:#?}"))
+        });
+    }
+}
+
+async fn persist_game(
+    db: &SqlitePool,
+    game: db::NewGame,
+    replay: Option<Vec<u8>>,
+    ended_at: u32,
+    stats: Vec<db::StatsUpdate>,
+    ranked: Vec<String>,
+    personal_best: Option<db::PersonalBestUpdate>,
+) -> sqlx::Result<()> {
+    let game_id = db::insert_game(db, &game).await?;
+
+    if let Some(replay) = replay {
+        db::insert_replay(db, game_id, &replay).await?;
+    }
+
+    for update in &stats {
+        db::update_stats(db, update).await?;
+    }
+
+    // leaderboards can't be farmed alone or against guests
+    if stats.len() >= 2 {
+        for update in &stats {
+            db::insert_leaderboard_result(db, game.game, ended_at, update).await?;
+        }
+    }
+
+    if let Some(personal_best) = personal_best {
+        db::update_personal_best(db, &personal_best, ended_at).await?;
+    }
+
+    if ranked.len() >= 2 {
+        db::update_ratings(
+            db,
+            &ranked,
+            game.game,
+            rating::STARTING_RATING,
+            rating::updated_ratings,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+impl PlayerStats {
+    fn personal_best(
+        &self,
+        discord_id: String,
+        game: &'static str,
+        duration: Duration,
+    ) -> db::PersonalBestUpdate {
+        match self {
+            PlayerStats::WordBomb { words_used, .. } => db::PersonalBestUpdate {
+                discord_id,
+                game,
+                score: *words_used,
+                survival_ms: Some(duration.as_millis() as u32),
+                completion: None,
+            },
+            PlayerStats::Anagrams {
+                points, completion, ..
+            } => db::PersonalBestUpdate {
+                discord_id,
+                game,
+                score: *points,
+                survival_ms: None,
+                completion: Some(*completion),
+            },
+            PlayerStats::Greentea { points } => db::PersonalBestUpdate {
+                discord_id,
+                game,
+                score: *points,
+                survival_ms: None,
+                completion: None,
+            },
+            PlayerStats::WordChain { words_used } => db::PersonalBestUpdate {
+                discord_id,
+                game,
+                score: *words_used,
+                survival_ms: Some(duration.as_millis() as u32),
+                completion: None,
+            },
+            PlayerStats::Boggle { points, completion } => db::PersonalBestUpdate {
+                discord_id,
+                game,
+                score: *points,
+                survival_ms: None,
+                completion: Some(*completion),
+            },
+            PlayerStats::Wordle { points } => db::PersonalBestUpdate {
+                discord_id,
+                game,
+                score: *points,
+                survival_ms: None,
+                completion: None,
+            },
+        }
+    }
+
+    fn into_update(self, discord_id: String, won: bool) -> Option<db::StatsUpdate> {
+        let update = match self {
+            PlayerStats::WordBomb {
+                words_used,
+                letters_used,
+                avg_wpm,
+                fastest_guess,
+                longest_word,
+            } => db::StatsUpdate::WordBomb(db::WordBombStatsUpdate {
+                discord_id,
+                won,
+                words_used,
+                letters_used,
+                avg_wpm: avg_wpm.unwrap_or_default(),
+                fastest_guess_ms: fastest_guess.map(|secs| (secs * 1000.0) as u32),
+                longest_word: longest_word.unwrap_or_default(),
+            }),
+            PlayerStats::Anagrams {
+                points,
+                words_found,
+                longest_word,
+                ..
+            } => db::StatsUpdate::Anagrams(db::AnagramsStatsUpdate {
+                discord_id,
+                won,
+                points,
+                words_found,
+                longest_word: longest_word.unwrap_or_default(),
+            }),
+            // only word bomb and anagrams keep account stats
+            PlayerStats::Greentea { .. }
+            | PlayerStats::WordChain { .. }
+            | PlayerStats::Wordle { .. }
+            | PlayerStats::Boggle { .. } => return None,
+        };
+
+        Some(update)
+    }
+}
@@ -1,11 +1,17 @@
 use crate::{
     global::GLOBAL,
     state::{
-        error::Result,
+        error::{Result, RoomError},
         games::{
-            anagrams::{self, Anagrams},
-            word_bomb::{self, WordBomb, WordBombSettings},
+            anagrams::Anagrams,
+            boggle::{self, Boggle},
+            greentea::Greentea,
+            word_bomb::WordBomb,
+            word_chain::WordChain,
+            wordle::{self, Wordle},
+            Context, Game,
         },
+        history::GameRecord,
         messages::{CountdownState, Games, PostGameInfo, RoomStateInfo, ServerMessage},
         room::{check_for_new_room_owner, Client, RoomSettings, State},
         Room, SenderInfo,
@@ -13,12 +19,10 @@ use crate::{
     utils::ClientUtils,
     AppState,
 };
-use rand::prelude::SliceRandom;
-use rand::{thread_rng, Rng};
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
-    time::{Duration, Instant},
+    time::Duration,
 };
 use tokio::task::AbortHandle;
 use uuid::Uuid;
@@ -42,72 +46,6 @@ impl Lobby {
             countdown: None,
         }
     }
-
-    pub fn start_word_bomb(
-        &self,
-        app_state: AppState,
-        room: String,
-        settings: WordBombSettings,
-    ) -> State {
-        let timer_len = thread_rng().gen_range(10.0..=30.0);
-        let prompt = GLOBAL.prompts.random_prompt(settings.min_wpm);
-        let mut players: Vec<word_bomb::Player> = self
-            .ready
-            .iter()
-            .map(|uuid| word_bomb::Player::new(*uuid))
-            .collect();
-        players.shuffle(&mut thread_rng());
-
-        let task = Arc::new(
-            tokio::spawn(async move {
-                app_state
-                    .word_bomb_timer(room, timer_len, prompt)
-                    .await
-                    .inspect_err(|error| eprintln!("word bomb timer error: {error:#?}"))
-            })
-            .abort_handle(),
-        );
-
-        State::WordBomb(WordBomb {
-            settings,
-            started_at: Instant::now(),
-            timer: word_bomb::Timer {
-                task,
-                start: Instant::now(),
-                length: timer_len,
-            },
-            prompt,
-            prompt_uses: 0,
-            missed_prompts: Vec::new(),
-            turn: players[0].uuid,
-            players,
-        })
-    }
-
-    pub fn start_anagrams(&self, app_state: AppState, room: String) -> State {
-        let (original, anagram) = GLOBAL.random_anagram();
-
-        let timer = Arc::new(
-            tokio::spawn(async move {
-                app_state
-                    .anagrams_timer(room)
-                    .await
-                    .inspect_err(|error| eprintln!("anagrams timer error: {error:#?}"))
-            })
-            .abort_handle(),
-        );
-
-        State::Anagrams(Anagrams {
-            timer,
-            anagram,
-            original: original.to_string(),
-            players: self
-                .ready
-                .iter()
-                .map(|uuid| anagrams::Player::new(*uuid))
-                .collect(),
-        })
-    }
 }
 
 impl AppState {
@@ -120,7 +58,8 @@ impl AppState {
             return Ok(());
         }
 
-        let countdown_update = check_for_countdown_update(self.clone(), room.to_string(), lobby);
+        let countdown_update =
+            check_for_countdown_update(self.clone(), room.to_string(), lobby, clients);
 
         clients.broadcast(ServerMessage::ReadyPlayers {
             ready: lobby.ready.iter().copied().collect(),
@@ -137,15 +76,26 @@ impl AppState {
             state,
             settings,
             owner,
+            used_words,
         } = lock.value_mut();
         let lobby = state.try_lobby()?;
 
-        if uuid == *owner && lobby.ready.len() >= 2 {
+        // a lone ready player plays solo, ranked games need an opponent
+        let min_players = if settings.ranked { 2 } else { 1 };
+
+        if uuid == *owner && lobby.ready.len() >= min_players && humans_ready(lobby, clients) {
             if let Some(countdown) = lobby.countdown.as_ref() {
                 countdown.timer_handle.abort();
             }
 
-            start_game(self.clone(), room.to_owned(), state, clients, settings)?;
+            start_game(
+                self.clone(),
+                room.to_owned(),
+                state,
+                clients,
+                settings,
+                used_words,
+            )?;
         }
 
         Ok(())
@@ -160,7 +110,8 @@ impl AppState {
             return Ok(());
         }
 
-        let countdown_update = check_for_countdown_update(self.clone(), room.to_string(), lobby);
+        let countdown_update =
+            check_for_countdown_update(self.clone(), room.to_string(), lobby, clients);
 
         clients.broadcast(ServerMessage::ReadyPlayers {
             ready: lobby.ready.iter().copied().collect(),
@@ -179,6 +130,7 @@ impl AppState {
                 clients,
                 state,
                 settings,
+                used_words,
                 ..
             } = lock.value_mut();
             let lobby = state.try_lobby()?;
@@ -191,7 +143,14 @@ impl AppState {
                         return Ok(());
                     }
 
-                    start_game(self.clone(), room.clone(), state, clients, settings)?;
+                    start_game(
+                        self.clone(),
+                        room.clone(),
+                        state,
+                        clients,
+                        settings,
+                        used_words,
+                    )?;
                 } else {
                     clients.broadcast(ServerMessage::StartingCountdown {
                         time_left: countdown.time_left,
@@ -213,16 +172,29 @@ impl AppState {
             clients, settings, ..
         } = lock.value();
 
+        let dictionary = GLOBAL.dictionary(&settings.dictionary);
+
         let set: Vec<String> = match game {
             Games::WordBomb => (0..50)
                 .map(|_| {
-                    GLOBAL
+                    dictionary
                         .prompts
                         .random_prompt(settings.word_bomb.min_wpm)
                         .to_string()
                 })
                 .collect(),
-            Games::Anagrams => (0..50).map(|_| GLOBAL.random_anagram().1).collect(),
+            Games::Anagrams => (0..50)
+                .filter_map(|_| dictionary.random_anagram(settings.anagrams.word_length))
+                .map(|(_, anagram)| anagram)
+                .collect(),
+            Games::Greentea => (0..50)
+                .map(|_| settings.greentea.random_letters())
+                .collect(),
+            Games::WordChain => (0..50)
+                .map(|_| settings.word_chain.random_prompt())
+                .collect(),
+            Games::Wordle => (0..50).map(|_| wordle::random_pattern()).collect(),
+            Games::Boggle => (0..50).map(|_| boggle::random_practice_grid()).collect(),
         };
 
         clients[&uuid].send(ServerMessage::PracticeSet { set });
@@ -238,17 +210,47 @@ impl AppState {
         input: &str,
     ) -> Result<()> {
         let lock = self.room(room)?;
-        let Room { clients, .. } = lock.value();
+        let Room {
+            clients, settings, ..
+        } = lock.value();
+
+        // the other games are only played in the default dictionary
+        let dictionary = match game {
+            Games::WordBomb | Games::Anagrams => GLOBAL.dictionary(&settings.dictionary),
+            _ => GLOBAL.default_dictionary(),
+        };
 
         let correct = match game {
-            Games::WordBomb => input.contains(prompt) && GLOBAL.is_valid(input),
+            Games::WordBomb => input.contains(prompt) && dictionary.is_valid(input),
             Games::Anagrams => {
                 input.len() >= 2
                     && !input
                         .chars()
                         .any(|ch| input.matches(ch).count() > prompt.matches(ch).count())
-                    && GLOBAL.is_valid(input)
+                    && dictionary.is_valid(input)
+            }
+            Games::WordChain => {
+                input.starts_with(prompt)
+                    && input.len() > prompt.len()
+                    && dictionary.is_valid(input)
+            }
+            Games::Greentea => {
+                !prompt
+                    .chars()
+                    .any(|ch| input.matches(ch).count() < prompt.matches(ch).count())
+                    && dictionary.is_valid(input)
+            }
+            Games::Wordle => {
+                input.len() == prompt.len()
+                    && prompt
+                        .chars()
+                        .zip(input.chars())
+                        .all(|(expected, actual)| expected == '_' || expected == actual)
+                    && dictionary.is_valid(input)
             }
+            Games::Boggle => boggle::parse_practice_grid(prompt).is_some_and(|grid| {
+                input.len() >= 3 && boggle::on_grid(&grid, input) && dictionary.is_valid(input)
+            }),
         };
 
         clients[&uuid].send(ServerMessage::PracticeResult { correct });
@@ -267,9 +269,39 @@ impl AppState {
             state,
             owner,
             settings,
+            ..
         } = lock.value_mut();
 
         if state.try_lobby().is_ok() && *owner == uuid {
+            settings_update.word_bomb.validate()?;
+            settings_update.anagrams.validate()?;
+            settings_update.greentea.validate()?;
+            settings_update.word_chain.validate()?;
+            settings_update.wordle.validate()?;
+            settings_update.boggle.validate()?;
+
+            if !GLOBAL.has_dictionary(&settings_update.dictionary) {
+                return Err(RoomError::UnknownDictionary {
+                    name: settings_update.dictionary,
+                })?;
+            }
+
+            if settings_update.game != Games::WordBomb
+                && clients.values().any(|client| client.bot.is_some())
+            {
+                return Err(RoomError::BotsUnsupported)?;
+            }
+
+            let settings_update = if settings_update.ranked {
+                if clients.values().any(|client| client.user.is_none()) {
+                    return Err(RoomError::GuestsInRankedRoom)?;
+                }
+
+                settings_update.ranked_preset()
+            } else {
+                settings_update
+            };
+
             settings.clone_from(&settings_update);
             clients.broadcast(ServerMessage::RoomSettings(settings_update));
         }
@@ -282,15 +314,19 @@ pub fn check_for_countdown_update(
     app_state: AppState,
     room: String,
     lobby: &mut Lobby,
+    clients: &HashMap<Uuid, Client>,
 ) -> Option<CountdownState> {
+    // bots are always ready, so they can't start a game on their own
+    let enough_ready = lobby.ready.len() >= 2 && humans_ready(lobby, clients);
+
     match &lobby.countdown {
-        Some(countdown) if lobby.ready.len() < 2 => {
+        Some(countdown) if !enough_ready => {
             countdown.timer_handle.abort();
             lobby.countdown = None;
 
             Some(CountdownState::Stopped)
         }
-        None if lobby.ready.len() >= 2 => {
+        None if enough_ready => {
             lobby.countdown = Some(Countdown {
                 timer_handle: Arc::new(
                     tokio::spawn(async move {
@@ -310,12 +346,20 @@ pub fn check_for_countdown_update(
     }
 }
 
+fn humans_ready(lobby: &Lobby, clients: &HashMap<Uuid, Client>) -> bool {
+    lobby
+        .ready
+        .iter()
+        .any(|uuid| clients.get(uuid).is_some_and(|client| client.bot.is_none()))
+}
+
 fn start_game(
     app_state: AppState,
     room: String,
     state: &mut State,
     clients: &mut HashMap<Uuid, Client>,
     settings: &RoomSettings,
+    used_words: &HashSet<String>,
 ) -> Result<()> {
     let lobby = state.try_lobby()?;
 
@@ -323,29 +367,29 @@ fn start_game(
         clients.get_mut(uuid).unwrap().rejoin_token = Some(Uuid::new_v4());
     }
 
+    let ctx = Context {
+        app_state: &app_state,
+        room: &room,
+        clients,
+    };
+
     let game = match settings.game {
         Games::WordBomb => {
-            *state = lobby.start_word_bomb(app_state, room, settings.word_bomb.clone());
-
-            let game = state.try_word_bomb()?;
-
-            RoomStateInfo::WordBomb {
-                turn: game.turn,
-                players: game.players.clone(),
-                prompt: game.prompt.to_string(),
-                used_letters: None,
-            }
-        }
-        Games::Anagrams => {
-            *state = lobby.start_anagrams(app_state, room);
+            let info = start::<WordBomb>(ctx, state, settings)?;
 
-            let game = state.try_anagrams()?;
+            // no repeats games carry on from the words used earlier in the room
+            state
+                .try_game::<WordBomb>()?
+                .session_words
+                .clone_from(used_words);
 
-            RoomStateInfo::Anagrams {
-                players: game.players.clone(),
-                anagram: game.anagram.clone(),
-            }
+            info
         }
+        Games::Anagrams => start::<Anagrams>(ctx, state, settings)?,
+        Games::Greentea => start::<Greentea>(ctx, state, settings)?,
+        Games::WordChain => start::<WordChain>(ctx, state, settings)?,
+        Games::Wordle => start::<Wordle>(ctx, state, settings)?,
+        Games::Boggle => start::<Boggle>(ctx, state, settings)?,
     };
 
     clients.send_each(|_uuid, client| ServerMessage::GameStarted {
@@ -356,12 +400,48 @@ fn start_game(
     Ok(())
 }
 
-pub fn end_game(
+fn start<G: Game + 'static>(
+    ctx: Context,
     state: &mut State,
-    clients: &mut HashMap<Uuid, Client>,
-    owner: &mut Uuid,
+    settings: &RoomSettings,
+) -> Result<RoomStateInfo> {
+    let mut game = G::start(ctx, &state.try_lobby()?.ready, settings);
+    let info = game.snapshot(None);
+
+    game.replay().record(&ServerMessage::GameStarted {
+        rejoin_token: None,
+        game: info.clone(),
+    });
+
+    *state = State::Game(Box::new(game));
+
+    Ok(info)
+}
+
+pub fn end_game(
+    app_state: &AppState,
+    room: &str,
+    Room {
+        owner,
+        settings,
+        clients,
+        state,
+        used_words,
+    }: &mut Room,
     info: PostGameInfo,
+    mut record: GameRecord,
 ) {
+    if settings.game == Games::WordBomb {
+        used_words.extend(record.words.iter().map(|(_, _, word)| word.clone()));
+    }
+
+    record.replay.record(&ServerMessage::GameEnded {
+        new_room_owner: None,
+        info: info.clone(),
+    });
+
+    app_state.save_game(room, settings, clients, record);
+
     clients.retain(|_uuid, client| client.socket.is_some());
 
     for client in clients.values_mut() {
@@ -375,5 +455,20 @@ pub fn end_game(
         info,
     });
 
-    *state = State::default();
+    let mut lobby = Lobby::new();
+    lobby.ready.extend(
+        clients
+            .iter()
+            .filter(|(_, client)| client.bot.is_some())
+            .map(|(uuid, _)| *uuid),
+    );
+
+    if !lobby.ready.is_empty() {
+        clients.broadcast(ServerMessage::ReadyPlayers {
+            ready: lobby.ready.iter().copied().collect(),
+            countdown_update: None,
+        });
+    }
+
+    *state = State::Lobby(lobby);
 }
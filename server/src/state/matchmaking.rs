@@ -0,0 +1,166 @@
+use crate::{
+    db,
+    routes::game::Params,
+    state::{
+        error::Result,
+        lobby::check_for_countdown_update,
+        messages::{Games, ServerMessage},
+        room::{Room, RoomSettings},
+    },
+    utils::{random_string, ClientUtils},
+    AppState,
+};
+use axum::extract::ws::Message;
+use rustrict::CensorStr;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use uuid::Uuid;
+
+pub const QUEUE_TIMEOUT: Duration = Duration::from_secs(20);
+const MIN_PLAYERS: usize = 2;
+const MAX_PLAYERS: usize = 6;
+const RATING_BAND: f64 = 200.0;
+// rating band widens by this much every second the oldest player has waited
+const RATING_BAND_GROWTH: f64 = 20.0;
+
+#[derive(Debug)]
+pub struct QueuedPlayer {
+    pub game: Games,
+    pub rating: f64,
+    pub joined: Instant,
+    pub params: Params,
+    pub user: Option<db::User>,
+    pub socket: Uuid,
+    pub tx: UnboundedSender<Message>,
+    pub matched: oneshot::Sender<(String, Uuid)>,
+}
+
+impl AppState {
+    pub fn join_queue(&self, player: QueuedPlayer) {
+        let game = player.game;
+
+        let mut queue = self.queue.lock().unwrap();
+        queue.push(player);
+        broadcast_queue_size(&queue, game);
+        drop(queue);
+
+        self.check_queue(game)
+            .unwrap_or_else(|e| eprintln!("failed to create match: {e}"));
+    }
+
+    pub fn leave_queue(&self, socket: Uuid) -> bool {
+        let mut queue = self.queue.lock().unwrap();
+
+        let Some(index) = queue.iter().position(|player| player.socket == socket) else {
+            return false;
+        };
+
+        let player = queue.remove(index);
+        broadcast_queue_size(&queue, player.game);
+
+        true
+    }
+
+    pub fn check_queue(&self, game: Games) -> Result<()> {
+        let mut queue = self.queue.lock().unwrap();
+
+        // queue is kept in join order, so the first match is the longest waiting
+        let Some(oldest) = queue.iter().find(|player| player.game == game) else {
+            return Ok(());
+        };
+
+        let waited = oldest.joined.elapsed();
+        let timed_out = waited >= QUEUE_TIMEOUT;
+        let band = RATING_BAND + waited.as_secs_f64() * RATING_BAND_GROWTH;
+        let rating = oldest.rating;
+
+        let candidates: Vec<usize> = queue
+            .iter()
+            .enumerate()
+            .filter(|(_, player)| {
+                player.game == game && (timed_out || (player.rating - rating).abs() <= band)
+            })
+            .map(|(index, _)| index)
+            .take(MAX_PLAYERS)
+            .collect();
+
+        if candidates.len() < MAX_PLAYERS && !(timed_out && candidates.len() >= MIN_PLAYERS) {
+            return Ok(());
+        }
+
+        let mut players: Vec<QueuedPlayer> = candidates
+            .into_iter()
+            .rev()
+            .map(|index| queue.remove(index))
+            .collect();
+        players.reverse();
+
+        broadcast_queue_size(&queue, game);
+        drop(queue);
+
+        self.create_match(game, players)
+    }
+
+    fn create_match(&self, game: Games, players: Vec<QueuedPlayer>) -> Result<()> {
+        let room = loop {
+            let name = random_string(6);
+
+            if self.room_available(&name) && !name.is_inappropriate() {
+                break name;
+            }
+        };
+
+        self.rooms.insert(
+            room.clone(),
+            Room {
+                settings: RoomSettings {
+                    game,
+                    public: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let mut ready = Vec::with_capacity(players.len());
+
+        for player in players {
+            player
+                .tx
+                .send(ServerMessage::MatchFound { room: room.clone() }.into())
+                .ok();
+
+            let uuid = self.add_client(&room, player.params, player.user, player.socket, player.tx);
+            player.matched.send((room.clone(), uuid)).ok();
+
+            ready.push(uuid);
+        }
+
+        let mut lock = self.room_mut(&room)?;
+        let Room { clients, state, .. } = lock.value_mut();
+        let lobby = state.try_lobby()?;
+
+        lobby.ready.extend(ready);
+
+        let countdown_update =
+            check_for_countdown_update(self.clone(), room.clone(), lobby, clients);
+
+        clients.broadcast(ServerMessage::ReadyPlayers {
+            ready: lobby.ready.iter().copied().collect(),
+            countdown_update,
+        });
+
+        Ok(())
+    }
+}
+
+fn broadcast_queue_size(queue: &[QueuedPlayer], game: Games) {
+    let waiting = queue.iter().filter(|player| player.game == game).count();
+
+    for player in queue.iter().filter(|player| player.game == game) {
+        player
+            .tx
+            .send(ServerMessage::QueueUpdate { waiting }.into())
+            .ok();
+    }
+}
@@ -1,5 +1,6 @@
 use crate::state::{
-    games::{anagrams, word_bomb},
+    bot::BotDifficulty,
+    games::{anagrams, boggle, greentea, word_bomb, word_chain, wordle},
     room::RoomSettings,
 };
 use axum::extract::ws::Message;
@@ -7,10 +8,27 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Games {
     WordBomb,
     Anagrams,
+    Greentea,
+    WordChain,
+    Wordle,
+    Boggle,
+}
+
+impl Games {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Games::WordBomb => "WordBomb",
+            Games::Anagrams => "Anagrams",
+            Games::Greentea => "Greentea",
+            Games::WordChain => "WordChain",
+            Games::Wordle => "Wordle",
+            Games::Boggle => "Boggle",
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -34,15 +52,39 @@ pub enum ClientMessage {
         input: String,
     },
     RoomSettings(RoomSettings),
+    AddBot {
+        difficulty: BotDifficulty,
+    },
+    RemoveBot {
+        uuid: Uuid,
+    },
     WordBombInput {
         input: String,
     },
     WordBombGuess {
         word: String,
     },
+    WordBombPowerUp {
+        power_up: word_bomb::PowerUp,
+    },
     AnagramsGuess {
         word: String,
     },
+    GreenteaGuess {
+        word: String,
+    },
+    WordChainInput {
+        input: String,
+    },
+    WordChainGuess {
+        word: String,
+    },
+    WordleGuess {
+        word: String,
+    },
+    BoggleGuess {
+        word: String,
+    },
 }
 
 #[derive(Serialize)]
@@ -51,6 +93,8 @@ pub enum ServerMessage {
     // lobby / generic
     Pong {
         timestamp: u64,
+        // unix ms, lets clients estimate their offset from the server clock
+        server_time: u64,
     },
     Info {
         uuid: Uuid,
@@ -81,6 +125,12 @@ pub enum ServerMessage {
         correct: bool,
     },
     RoomSettings(RoomSettings),
+    QueueUpdate {
+        waiting: usize,
+    },
+    MatchFound {
+        room: String,
+    },
     GameStarted {
         rejoin_token: Option<Uuid>,
         game: RoomStateInfo,
@@ -103,6 +153,17 @@ pub enum ServerMessage {
         life_change: i8,
         prompt: String,
         turn: Uuid,
+        deadline: u64,
+    },
+    WordBombPowerUpEarned {
+        uuid: Uuid,
+        power_up: word_bomb::PowerUp,
+    },
+    WordBombPowerUpUsed {
+        uuid: Uuid,
+        power_up: word_bomb::PowerUp,
+        turn: Uuid,
+        deadline: u64,
     },
     // anagrams
     AnagramsInvalidGuess {
@@ -112,6 +173,79 @@ pub enum ServerMessage {
         uuid: Uuid,
         guess: String,
     },
+    AnagramsRoundEnded {
+        round: u8,
+        original_word: String,
+        possible_words: anagrams::PossibleWords,
+        completion: Vec<(Uuid, f32)>,
+        // cumulative over every round so far
+        leaderboard: Vec<(Uuid, u32)>,
+    },
+    AnagramsRoundStarted {
+        round: u8,
+        anagram: String,
+    },
+    // greentea
+    GreenteaInvalidGuess {
+        reason: greentea::GuessInfo,
+    },
+    // the word itself is only revealed once the round ends
+    GreenteaCorrectGuess {
+        uuid: Uuid,
+        points: u32,
+    },
+    GreenteaRoundEnded {
+        round: u8,
+        letters: String,
+        // (uuid, word, points)
+        guesses: Vec<(Uuid, String, u32)>,
+        // cumulative over every round so far
+        leaderboard: Vec<(Uuid, u32)>,
+    },
+    GreenteaRoundStarted {
+        round: u8,
+        letters: String,
+        deadline: u64,
+    },
+    // word chain
+    WordChainInput {
+        uuid: Uuid,
+        input: String,
+    },
+    WordChainInvalidGuess {
+        uuid: Uuid,
+        reason: word_chain::GuessInfo,
+    },
+    WordChainPrompt {
+        correct_guess: Option<String>,
+        life_change: i8,
+        prompt: String,
+        turn: Uuid,
+        deadline: u64,
+    },
+    // wordle
+    WordleInvalidGuess {
+        reason: wordle::GuessInfo,
+    },
+    // only sent to the player who guessed
+    WordleFeedback {
+        guess: wordle::Guess,
+    },
+    // what everyone else gets to see of a guess
+    WordleProgress {
+        uuid: Uuid,
+        guesses: u8,
+        correct: u8,
+        solved: bool,
+    },
+    // boggle
+    BoggleInvalidGuess {
+        reason: boggle::GuessInfo,
+    },
+    BoggleCorrectGuess {
+        uuid: Uuid,
+        guess: String,
+    },
 }
 
 #[derive(Serialize)]
@@ -133,11 +267,39 @@ pub enum RoomStateInfo {
         players: Vec<word_bomb::Player>,
         turn: Uuid,
         prompt: String,
+        deadline: u64,
         used_letters: Option<HashSet<char>>,
     },
     Anagrams {
         players: Vec<anagrams::Player>,
         anagram: String,
+        round: u8,
+        intermission: bool,
+    },
+    Greentea {
+        players: Vec<greentea::Player>,
+        letters: String,
+        round: u8,
+        intermission: bool,
+        deadline: u64,
+        submitted: Vec<Uuid>,
+    },
+    WordChain {
+        players: Vec<word_chain::Player>,
+        turn: Uuid,
+        prompt: String,
+        deadline: u64,
+    },
+    Wordle {
+        players: Vec<wordle::Player>,
+        deadline: u64,
+        // the receiving player's own guesses
+        board: Vec<wordle::Guess>,
+    },
+    Boggle {
+        players: Vec<anagrams::Player>,
+        grid: boggle::Grid,
+        deadline: u64,
     },
 }
 
@@ -146,6 +308,10 @@ pub enum RoomStateInfo {
 pub enum PostGameInfo {
     WordBomb(word_bomb::PostGameInfo),
     Anagrams(anagrams::PostGameInfo),
+    Greentea(greentea::PostGameInfo),
+    WordChain(word_chain::PostGameInfo),
+    Wordle(wordle::PostGameInfo),
+    Boggle(boggle::PostGameInfo),
 }
 
 #[derive(Serialize)]
@@ -153,14 +319,34 @@ pub struct ClientInfo {
     pub uuid: Uuid,
     pub username: String,
     pub disconnected: bool,
+    pub account: Option<AccountInfo>,
+    pub verified: bool,
+    pub bot: bool,
+}
+
+#[derive(Serialize, Clone)]
+pub struct AccountInfo {
+    pub username: String,
+    pub avatar_hash: String,
 }
 
 #[derive(Serialize)]
 #[serde(tag = "type")]
 pub enum ConnectionUpdate {
-    Connected { username: String },
-    Reconnected { username: String },
-    Disconnected { new_room_owner: Option<Uuid> },
+    Connected {
+        username: String,
+        account: Option<AccountInfo>,
+        verified: bool,
+        bot: bool,
+    },
+    Reconnected {
+        username: String,
+        account: Option<AccountInfo>,
+        verified: bool,
+    },
+    Disconnected {
+        new_room_owner: Option<Uuid>,
+    },
 }
 
 #[derive(Serialize)]
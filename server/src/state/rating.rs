@@ -0,0 +1,74 @@
+pub const STARTING_RATING: f64 = 1200.0;
+const K_FACTOR: f64 = 32.0;
+
+// every pair of players is scored as a 1v1 that the better placed player won,
+// then the total change is scaled down so bigger games don't swing ratings more
+pub fn updated_ratings(placements: &[f64]) -> Vec<f64> {
+    let opponents = placements.len().saturating_sub(1).max(1) as f64;
+
+    placements
+        .iter()
+        .enumerate()
+        .map(|(i, rating)| {
+            let change: f64 = placements
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| i != *j)
+                .map(|(j, other)| {
+                    let expected = 1.0 / (1.0 + 10_f64.powf((other - rating) / 400.0));
+                    let score = if i < j { 1.0 } else { 0.0 };
+
+                    score - expected
+                })
+                .sum();
+
+            rating + K_FACTOR * change / opponents
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evenly_matched_winner_takes_half_the_k_factor() {
+        let ratings = updated_ratings(&[STARTING_RATING, STARTING_RATING]);
+
+        assert_eq!(ratings, [STARTING_RATING + 16.0, STARTING_RATING - 16.0]);
+    }
+
+    #[test]
+    fn rating_changes_cancel_out() {
+        let before = [1500.0, 1200.0, 1350.0, 1000.0];
+        let after = updated_ratings(&before);
+        let change: f64 = after.iter().zip(before).map(|(a, b)| a - b).sum();
+
+        assert!(change.abs() < 1e-9);
+        // the first player beat everyone and the last lost to everyone
+        assert!(after[0] > before[0]);
+        assert!(after[3] < before[3]);
+    }
+
+    #[test]
+    fn upsets_move_ratings_further() {
+        let expected = updated_ratings(&[1600.0, 1200.0]);
+        let upset = updated_ratings(&[1200.0, 1600.0]);
+
+        assert!(upset[0] - 1200.0 > expected[0] - 1600.0);
+    }
+
+    #[test]
+    fn bigger_games_dont_swing_ratings_more() {
+        let big = updated_ratings(&[STARTING_RATING; 8]);
+
+        assert_eq!(big[0], STARTING_RATING + 16.0);
+        assert_eq!(big[7], STARTING_RATING - 16.0);
+    }
+
+    #[test]
+    fn single_player_keeps_their_rating() {
+        assert_eq!(updated_ratings(&[1300.0]), [1300.0]);
+        assert!(updated_ratings(&[]).is_empty());
+    }
+}
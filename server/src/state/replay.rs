@@ -0,0 +1,49 @@
+use crate::state::messages::ServerMessage;
+use flate2::{write::GzEncoder, Compression};
+use std::{io::Write, time::Instant};
+
+#[derive(Debug)]
+pub struct Replay {
+    started_at: Instant,
+    // (ms since the game started, serialized message)
+    events: Vec<(u32, String)>,
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Self::new(Instant::now())
+    }
+}
+
+impl Replay {
+    pub fn new(started_at: Instant) -> Self {
+        Self {
+            started_at,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, message: &ServerMessage) {
+        let offset = self.started_at.elapsed().as_millis() as u32;
+        self.events
+            .push((offset, serde_json::to_string(message).unwrap()));
+    }
+
+    // gzipped json array of [offset, message] pairs
+    pub fn compress(&self) -> std::io::Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+
+        encoder.write_all(b"[")?;
+
+        for (i, (offset, message)) in self.events.iter().enumerate() {
+            if i != 0 {
+                encoder.write_all(b",")?;
+            }
+
+            write!(encoder, "[{offset},{message}]")?;
+        }
+
+        encoder.write_all(b"]")?;
+        encoder.finish()
+    }
+}
@@ -1,60 +1,132 @@
 use crate::{
+    db,
+    global::DEFAULT_DICTIONARY,
     routes::game::Params,
     state::{
+        bot::BotDifficulty,
         error::{GameError, Result, RoomError},
         games::{
-            anagrams::Anagrams,
-            word_bomb::{WordBomb, WordBombSettings},
+            anagrams::AnagramsSettings, apply_update, boggle::BoggleSettings,
+            greentea::GreenteaSettings, word_bomb::WordBombSettings, word_chain::WordChainSettings,
+            wordle::WordleSettings, Game,
         },
         lobby::{check_for_countdown_update, Lobby},
-        messages::{ClientInfo, ConnectionUpdate, Games, RoomInfo, RoomStateInfo, ServerMessage},
+        messages::{
+            AccountInfo, ClientInfo, ConnectionUpdate, Games, RoomInfo, RoomStateInfo,
+            ServerMessage,
+        },
         SenderInfo,
     },
-    utils::ClientUtils,
+    utils::{ClientUtils, UnixTime},
     AppState,
 };
 use axum::extract::ws::{close_code, CloseFrame, Message};
-use rand::{seq::IteratorRandom, thread_rng};
+use rand::{seq::IteratorRandom, thread_rng, Rng};
 use rustrict::CensorStr;
 use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant, SystemTime},
+};
 use tokio::sync::mpsc::UnboundedSender;
 use uuid::Uuid;
 
+// caps how much a slow connection (or one sitting on its pongs) can be credited
+const MAX_LATENCY_ALLOWANCE: Duration = Duration::from_millis(200);
+
 #[derive(Debug, Default)]
 pub struct Room {
     pub owner: Uuid,
     pub settings: RoomSettings,
     pub clients: HashMap<Uuid, Client>,
     pub state: State,
+    // every word played in the room's word bomb games so far
+    pub used_words: HashSet<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RoomSettings {
     pub game: Games,
     pub public: bool,
+    pub ranked: bool,
+    // word bomb, anagrams and practice are played in this dictionary
+    #[serde(default = "default_dictionary")]
+    pub dictionary: String,
     pub word_bomb: WordBombSettings,
+    #[serde(default)]
+    pub anagrams: AnagramsSettings,
+    #[serde(default)]
+    pub greentea: GreenteaSettings,
+    #[serde(default)]
+    pub word_chain: WordChainSettings,
+    #[serde(default)]
+    pub wordle: WordleSettings,
+    #[serde(default)]
+    pub boggle: BoggleSettings,
 }
 
 impl Default for RoomSettings {
     fn default() -> Self {
         Self {
             public: false,
+            ranked: false,
             game: Games::WordBomb,
-            word_bomb: WordBombSettings { min_wpm: 500 },
+            dictionary: default_dictionary(),
+            word_bomb: WordBombSettings::default(),
+            anagrams: AnagramsSettings::default(),
+            greentea: GreenteaSettings::default(),
+            word_chain: WordChainSettings::default(),
+            wordle: WordleSettings::default(),
+            boggle: BoggleSettings::default(),
         }
     }
 }
 
+impl RoomSettings {
+    // ranked games are always played on the standard rules
+    pub fn ranked_preset(self) -> Self {
+        Self {
+            ranked: true,
+            dictionary: default_dictionary(),
+            word_bomb: WordBombSettings::default(),
+            anagrams: AnagramsSettings::default(),
+            greentea: GreenteaSettings::default(),
+            word_chain: WordChainSettings::default(),
+            // a daily word could be looked up by anyone who already played it today
+            wordle: WordleSettings {
+                daily: false,
+                ..WordleSettings::default()
+            },
+            boggle: BoggleSettings::default(),
+            ..self
+        }
+    }
+}
+
+fn default_dictionary() -> String {
+    DEFAULT_DICTIONARY.to_string()
+}
+
 #[derive(Debug)]
 pub struct Client {
     pub socket: Option<Uuid>,
     pub tx: UnboundedSender<Message>,
     pub username: String,
+    pub user: Option<db::User>,
     pub rejoin_token: Option<Uuid>,
+    // round trip time of the last ping the server sent, answered by the client's pong
+    pub latency: Duration,
+    pub pending_ping: Option<(u64, Instant)>,
+    pub bot: Option<BotDifficulty>,
 }
 
 impl Client {
+    // time a message from this client is assumed to have spent in flight
+    pub fn latency_allowance(&self) -> Duration {
+        (self.latency / 2).min(MAX_LATENCY_ALLOWANCE)
+    }
+
     pub fn send(&self, message: ServerMessage) {
         self.tx.send(message.into()).ok();
     }
@@ -62,13 +134,37 @@ impl Client {
     pub fn close(&self, close_frame: Option<CloseFrame<'static>>) {
         self.tx.send(Message::Close(close_frame)).ok();
     }
+
+    pub fn account(&self) -> Option<AccountInfo> {
+        self.user.as_ref().map(|user| AccountInfo {
+            username: user.username.clone(),
+            avatar_hash: user.avatar_hash.clone(),
+        })
+    }
+
+    // usernames are reserved case insensitively, and sqlite's nocase only folds ascii
+    pub fn verified(&self) -> bool {
+        self.user
+            .as_ref()
+            .is_some_and(|user| user.username.eq_ignore_ascii_case(&self.username))
+    }
+
+    pub fn info(&self, uuid: Uuid) -> ClientInfo {
+        ClientInfo {
+            uuid,
+            username: self.username.clone(),
+            disconnected: self.socket.is_none(),
+            account: self.account(),
+            verified: self.verified(),
+            bot: self.bot.is_some(),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum State {
     Lobby(Lobby),
-    WordBomb(WordBomb),
-    Anagrams(Anagrams),
+    Game(Box<dyn Game>),
 }
 
 impl Default for State {
@@ -84,20 +180,6 @@ impl State {
             _ => Err(GameError::InvalidState { state: "lobby" }),
         }
     }
-
-    pub fn try_word_bomb(&mut self) -> Result<&mut WordBomb, GameError> {
-        match self {
-            State::WordBomb(game) => Ok(game),
-            _ => Err(GameError::InvalidState { state: "word bomb" }),
-        }
-    }
-
-    pub fn try_anagrams(&mut self) -> Result<&mut Anagrams, GameError> {
-        match self {
-            State::Anagrams(game) => Ok(game),
-            _ => Err(GameError::InvalidState { state: "anagrams" }),
-        }
-    }
 }
 
 impl AppState {
@@ -105,10 +187,15 @@ impl AppState {
         self.room(room).is_ok_and(|room| room.clients.len() >= 8)
     }
 
+    pub fn room_ranked(&self, room: &str) -> bool {
+        self.room(room).is_ok_and(|room| room.settings.ranked)
+    }
+
     pub fn add_client(
         &self,
         room: &str,
         params: Params,
+        user: Option<db::User>,
         socket_uuid: Uuid,
         tx: UnboundedSender<Message>,
     ) -> Uuid {
@@ -118,6 +205,7 @@ impl AppState {
             state,
             owner,
             settings,
+            ..
         } = lock.value_mut();
 
         let prev_client = params.rejoin_token.and_then(|rejoin_token| {
@@ -139,6 +227,9 @@ impl AppState {
             client.socket = Some(socket_uuid);
             client.tx = tx;
             client.username.clone_from(&params.username);
+            client.user = user;
+            client.latency = Duration::ZERO;
+            client.pending_ping = None;
 
             let uuid = *prev_uuid;
 
@@ -146,6 +237,8 @@ impl AppState {
                 uuid,
                 ConnectionUpdate::Reconnected {
                     username: params.username,
+                    account: client.account(),
+                    verified: client.verified(),
                 },
             )
         } else {
@@ -155,22 +248,27 @@ impl AppState {
                 *owner = uuid;
             }
 
-            clients.insert(
-                uuid,
-                Client {
-                    socket: Some(socket_uuid),
-                    tx,
-                    username: params.username.clone(),
-                    rejoin_token: None,
-                },
-            );
-
-            (
-                uuid,
-                ConnectionUpdate::Connected {
-                    username: params.username.clone(),
-                },
-            )
+            let client = Client {
+                socket: Some(socket_uuid),
+                tx,
+                username: params.username.clone(),
+                user,
+                rejoin_token: None,
+                latency: Duration::ZERO,
+                pending_ping: None,
+                bot: None,
+            };
+
+            let connection_update = ConnectionUpdate::Connected {
+                username: params.username,
+                account: client.account(),
+                verified: client.verified(),
+                bot: false,
+            };
+
+            clients.insert(uuid, client);
+
+            (uuid, connection_update)
         };
 
         clients.broadcast_except(
@@ -188,11 +286,7 @@ impl AppState {
                 settings: settings.clone(),
                 clients: clients
                     .iter()
-                    .map(|(uuid, client)| ClientInfo {
-                        uuid: *uuid,
-                        username: client.username.clone(),
-                        disconnected: client.socket.is_none(),
-                    })
+                    .map(|(uuid, client)| client.info(*uuid))
                     .collect(),
                 state: room_state_info(state, uuid),
             },
@@ -218,19 +312,16 @@ impl AppState {
             .get_mut(&uuid)
             .ok_or(RoomError::CouldntFindClientToRemove)?;
 
-        if !client
-            .socket
-            .is_some_and(|client_socket_id| client_socket_id == socket_id)
-        {
+        if client.socket != Some(socket_id) {
             return Err(RoomError::SocketUuidMismatchWhileRemoving)?;
         }
 
         client.socket = None;
 
-        if clients.connected().count() == 0 {
+        // bots can't keep a room alive on their own
+        if clients.connected().all(|(_, client)| client.bot.is_some()) {
             match state {
-                State::WordBomb(game) => game.timer.task.abort(),
-                State::Anagrams(game) => game.timer.abort(),
+                State::Game(game) => game.abort(),
                 State::Lobby(_) => {}
             }
 
@@ -244,7 +335,7 @@ impl AppState {
 
             if lobby.ready.remove(&uuid) {
                 let countdown_update =
-                    check_for_countdown_update(self.clone(), room.to_string(), lobby);
+                    check_for_countdown_update(self.clone(), room.to_string(), lobby, clients);
 
                 clients.broadcast(ServerMessage::ReadyPlayers {
                     ready: lobby.ready.iter().copied().collect(),
@@ -265,6 +356,12 @@ impl AppState {
                     new_room_owner: None,
                 },
             });
+
+            if let State::Game(_) = state {
+                return apply_update(self, room, lock.value_mut(), |game, ctx| {
+                    game.on_disconnect(ctx, uuid)
+                });
+            }
         }
 
         Ok(())
@@ -274,7 +371,42 @@ impl AppState {
         let lock = self.room(room)?;
         let Room { clients, .. } = lock.value();
 
-        clients[&uuid].send(ServerMessage::Pong { timestamp });
+        clients[&uuid].send(ServerMessage::Pong {
+            timestamp,
+            server_time: SystemTime::now().to_unix_millis(),
+        });
+
+        Ok(())
+    }
+
+    // latency is measured with the server's own pings, so clients can't report whatever they like
+    pub fn ping_client(&self, SenderInfo { uuid, room }: SenderInfo) -> Result<()> {
+        let mut lock = self.room_mut(room)?;
+        let Room { clients, .. } = lock.value_mut();
+        let client = clients.get_mut(&uuid).ok_or(RoomError::ClientNotFound)?;
+
+        let nonce: u64 = thread_rng().gen();
+        client.pending_ping = Some((nonce, Instant::now()));
+        client
+            .tx
+            .send(Message::Ping(nonce.to_be_bytes().to_vec()))
+            .ok();
+
+        Ok(())
+    }
+
+    pub fn client_pong(&self, SenderInfo { uuid, room }: SenderInfo, payload: &[u8]) -> Result<()> {
+        let mut lock = self.room_mut(room)?;
+        let Room { clients, .. } = lock.value_mut();
+        let client = clients.get_mut(&uuid).ok_or(RoomError::ClientNotFound)?;
+
+        // only the latest ping counts, older or made up pongs are ignored
+        if let Some((nonce, sent)) = client.pending_ping {
+            if payload == nonce.to_be_bytes() {
+                client.latency = sent.elapsed();
+                client.pending_ping = None;
+            }
+        }
 
         Ok(())
     }
@@ -330,20 +462,7 @@ fn room_state_info(state: &State, uuid: Uuid) -> RoomStateInfo {
                 .as_ref()
                 .map(|countdown| countdown.time_left),
         },
-        State::WordBomb(game) => RoomStateInfo::WordBomb {
-            players: game.players.clone(),
-            turn: game.turn,
-            prompt: game.prompt.to_string(),
-            used_letters: game
-                .players
-                .iter()
-                .find(|player| uuid == player.uuid)
-                .map(|player| player.used_letters.clone()),
-        },
-        State::Anagrams(game) => RoomStateInfo::Anagrams {
-            players: game.players.clone(),
-            anagram: game.anagram.clone(),
-        },
+        State::Game(game) => game.snapshot(Some(uuid)),
     }
 }
 
@@ -351,7 +470,11 @@ pub fn check_for_new_room_owner(clients: &HashMap<Uuid, Client>, owner: &mut Uui
     if clients.get(owner).is_some() {
         None
     } else {
-        *owner = *clients.keys().choose(&mut thread_rng()).unwrap();
+        *owner = *clients
+            .iter()
+            .filter(|(_, client)| client.bot.is_none())
+            .map(|(uuid, _)| uuid)
+            .choose(&mut thread_rng())?;
         Some(*owner)
     }
 }
@@ -1,4 +1,4 @@
-use crate::state::{messages::ServerMessage, room::Client};
+use crate::state::{messages::ServerMessage, replay::Replay, room::Client};
 use axum::extract::ws::Message;
 use rand::{
     distributions::{Alphanumeric, DistString},
@@ -13,8 +13,10 @@ use uuid::Uuid;
 
 pub trait ClientUtils {
     fn connected(&self) -> impl Iterator<Item = (&Uuid, &Client)>;
+    fn is_connected(&self, uuid: Uuid) -> bool;
     fn send_each(&self, f: impl Fn(&Uuid, &Client) -> ServerMessage);
     fn broadcast(&self, message: ServerMessage);
+    fn broadcast_recorded(&self, message: ServerMessage, replay: &mut Replay);
     fn broadcast_except(&self, message: ServerMessage, except: &[Uuid]);
 }
 
@@ -23,6 +25,11 @@ impl ClientUtils for HashMap<Uuid, Client> {
         self.iter().filter(|client| client.1.socket.is_some())
     }
 
+    fn is_connected(&self, uuid: Uuid) -> bool {
+        self.get(&uuid)
+            .is_some_and(|client| client.socket.is_some())
+    }
+
     fn send_each(&self, f: impl Fn(&Uuid, &Client) -> ServerMessage) {
         for (uuid, client) in self.connected() {
             client.send(f(uuid, client));
@@ -37,6 +44,11 @@ impl ClientUtils for HashMap<Uuid, Client> {
         }
     }
 
+    fn broadcast_recorded(&self, message: ServerMessage, replay: &mut Replay) {
+        replay.record(&message);
+        self.broadcast(message);
+    }
+
     fn broadcast_except(&self, message: ServerMessage, except: &[Uuid]) {
         let serialized: Message = message.into();
 
@@ -51,6 +63,7 @@ impl ClientUtils for HashMap<Uuid, Client> {
 
 pub trait UnixTime {
     fn to_unix_timestamp(&self) -> u32;
+    fn to_unix_millis(&self) -> u64;
 }
 
 impl UnixTime for SystemTime {
@@ -59,6 +72,12 @@ impl UnixTime for SystemTime {
             .expect("we have time traveled to before 1970-01-01 00:00:00 UTC")
             .as_secs() as u32
     }
+
+    fn to_unix_millis(&self) -> u64 {
+        self.duration_since(UNIX_EPOCH)
+            .expect("we have time traveled to before 1970-01-01 00:00:00 UTC")
+            .as_millis() as u64
+    }
 }
 
 // https://docs.rs/itertools/0.9.0/src/itertools/lib.rs.html#2061